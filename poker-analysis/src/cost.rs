@@ -0,0 +1,88 @@
+//! The cost of playing by a simplified strategy instead of the optimal one.
+
+use core::fmt;
+
+use poker_base::Card;
+
+use crate::{draw::DrawTable, policy::Policy, report};
+
+/// Two holds whose expected values differ by less count as equally good.
+const TOLERANCE: f64 = 1e-9;
+
+/// A hand the strategy plays worse than the optimal one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deviation {
+    /// The canonical representative of the hand's suit-isomorphism class.
+    pub hand: [Card; 5],
+    pub keep: Vec<usize>,
+    pub optimal: Vec<usize>,
+    /// The expected value lost each time the hand is dealt.
+    pub loss: f64,
+    /// The probability of being dealt any hand of the class.
+    pub probability: f64,
+}
+
+impl Deviation {
+    /// The part of the return lost on this hand.
+    pub fn cost(&self) -> f64 {
+        self.loss * self.probability
+    }
+}
+
+/// The return of a strategy compared to the optimal one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cost {
+    pub optimal_return: f64,
+    pub strategy_return: f64,
+    /// The probability of being dealt a hand the strategy plays worse.
+    pub deviation_frequency: f64,
+    /// Ordered from the most to the least expensive.
+    pub deviations: Vec<Deviation>,
+}
+
+impl Cost {
+    /// Plays every possible deal by both the strategy and the optimal policy (e.g. [crate::policy::Optimal]).
+    pub fn measure(table: &DrawTable, strategy: &impl Policy, optimal: &impl Policy) -> Self {
+        let paytable = table.paytable();
+
+        let mut cost = Cost { optimal_return: 0f64, strategy_return: 0f64, deviation_frequency: 0f64, deviations: Vec::new() };
+
+        for hand in report::classes(table) {
+            let probability = report::class_weight(table, &hand);
+
+            let (keep, optimal) = (strategy.hold(&hand), optimal.hold(&hand));
+            let optimal_value = table.outcome(&hand, &optimal).expected_value(paytable);
+
+            let value = match keep == optimal {
+                true => optimal_value,
+                false => table.outcome(&hand, &keep).expected_value(paytable),
+            };
+
+            cost.optimal_return += probability * optimal_value;
+            cost.strategy_return += probability * value;
+
+            if optimal_value - value > TOLERANCE {
+                cost.deviation_frequency += probability;
+                cost.deviations.push(Deviation { hand, keep, optimal, loss: optimal_value - value, probability });
+            }
+        }
+
+        cost.deviations.sort_by(|a, b| b.cost().total_cmp(&a.cost()));
+
+        cost
+    }
+
+    /// The part of the return lost by playing the strategy.
+    pub fn lost(&self) -> f64 {
+        self.optimal_return - self.strategy_return
+    }
+}
+
+impl fmt::Display for Cost {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "Optimal return:     {:.4}%", self.optimal_return * 100f64)?;
+        writeln!(formatter, "Strategy return:    {:.4}%", self.strategy_return * 100f64)?;
+        writeln!(formatter, "Return lost:        {:.4}%", self.lost() * 100f64)?;
+        writeln!(formatter, "Deviating deals:    {:.4}%", self.deviation_frequency * 100f64)
+    }
+}
@@ -0,0 +1,272 @@
+//! Differences between two computed strategies, e.g. before and after changing a paytable or fixing an evaluator.
+//! Strategies are compared pattern by pattern from their state files. Exported charts only hold the aggregates
+//! of each hold class, so they are compared hold class by hold class.
+
+use core::fmt;
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use poker_base::{Card, ComputedMove, GameVariant};
+
+use crate::{export::ExportedEntry, hold::{self, HoldClass}, state, strategy::Strategy};
+
+/// Expected values differing by less count as equal.
+const TOLERANCE: f64 = 1e-9;
+
+/// A pattern whose optimal hold differs between the strategies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternChange {
+    pub hand: [Card; 5],
+    pub old_keep: Vec<usize>,
+    pub new_keep: Vec<usize>,
+    pub old_score: f64,
+    pub new_score: f64,
+    /// The probability of being dealt the pattern.
+    pub probability: f64,
+}
+
+impl PatternChange {
+    pub fn delta(&self) -> f64 {
+        self.new_score - self.old_score
+    }
+}
+
+/// The compared patterns whose old and new holds fall into the same pattern classes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassChange {
+    pub old: HoldClass,
+    pub new: HoldClass,
+    pub patterns: usize,
+    /// The probability of being dealt any of the patterns.
+    pub probability: f64,
+    /// The part of the return the patterns change.
+    pub contribution: f64,
+}
+
+impl ClassChange {
+    /// Whether the hold itself changes, not only its expected value.
+    pub fn hold_changed(&self) -> bool {
+        self.old != self.new
+    }
+
+    /// The average change of the expected value of the patterns.
+    pub fn delta(&self) -> f64 {
+        self.contribution / self.probability
+    }
+}
+
+/// The differences of a new strategy to an old one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diff {
+    pub old_return: f64,
+    pub new_return: f64,
+    /// The probability of being dealt a pattern computed by the old strategy.
+    pub old_coverage: f64,
+    pub new_coverage: f64,
+    /// The probability of being dealt a pattern computed by both strategies.
+    pub compared: f64,
+    /// Ordered from the largest to the smallest change of the return.
+    pub classes: Vec<ClassChange>,
+    /// Ordered from the largest to the smallest change of the return.
+    pub changes: Vec<PatternChange>,
+}
+
+impl Diff {
+    /// Compares the moves pattern by pattern. Patterns are matched by their suit-isomorphism class, so
+    /// full and canonicalised sets of patterns can be compared. The moves must belong to the given game.
+    pub fn compare(game: &impl GameVariant, old: &[ComputedMove], new: &[ComputedMove]) -> Self {
+        let deck_size = game.deck().len();
+        let strategy = Strategy::new(new.iter().cloned());
+
+        let mut diff = Diff {
+            old_return: 0f64,
+            new_return: 0f64,
+            old_coverage: 0f64,
+            new_coverage: 0f64,
+            compared: 0f64,
+            classes: Vec::new(),
+            changes: Vec::new(),
+        };
+
+        for (weight, r#move) in state::weigh(new, deck_size) {
+            diff.new_return += weight * r#move.average_score;
+            diff.new_coverage += weight;
+        }
+
+        let mut classes: HashMap<(HoldClass, HoldClass), ClassChange> = HashMap::new();
+
+        for (weight, old) in state::weigh(old, deck_size) {
+            diff.old_return += weight * old.average_score;
+            diff.old_coverage += weight;
+
+            let Some(new) = strategy.lookup(&old.pattern) else { continue };
+
+            diff.compared += weight;
+
+            let mut old_keep = old.keep.clone();
+            old_keep.sort_unstable();
+
+            let delta = new.average_score - old.average_score;
+            // of equally good holds, the new one is no change.
+            let changed = old_keep != new.keep && delta.abs() > TOLERANCE;
+
+            let old_class = hold::classify(&old.pattern, &old_keep, |card| game.is_wild(card));
+            let new_class = match changed {
+                true => hold::classify(&new.pattern, &new.keep, |card| game.is_wild(card)),
+                false => old_class.clone(),
+            };
+
+            let class = classes.entry((old_class.clone(), new_class.clone())).or_insert(ClassChange {
+                old: old_class,
+                new: new_class,
+                patterns: 0,
+                probability: 0f64,
+                contribution: 0f64,
+            });
+
+            class.patterns += 1;
+            class.probability += weight;
+            class.contribution += weight * delta;
+
+            if changed {
+                diff.changes.push(PatternChange {
+                    hand: old.pattern,
+                    old_keep,
+                    new_keep: new.keep,
+                    old_score: old.average_score,
+                    new_score: new.average_score,
+                    probability: weight,
+                });
+            }
+        }
+
+        diff.classes = classes
+            .into_values()
+            .filter(|class| class.hold_changed() || class.delta().abs() > TOLERANCE)
+            .collect();
+
+        diff.classes.sort_by(|a, b| b.contribution.abs().total_cmp(&a.contribution.abs()).then_with(|| (&a.old, &a.new).cmp(&(&b.old, &b.new))));
+        diff.changes.sort_by(|a, b| (b.probability * b.delta().abs()).total_cmp(&(a.probability * a.delta().abs())));
+
+        diff
+    }
+
+    /// The change of the return over the deals computed by both strategies.
+    pub fn return_change(&self) -> f64 {
+        self.classes.iter().map(|class| class.contribution).sum()
+    }
+
+    /// The probability of being dealt a pattern whose optimal hold changes.
+    pub fn changed_frequency(&self) -> f64 {
+        self.changes.iter().map(|change| change.probability).sum()
+    }
+}
+
+/// The return over the covered deals, if there are any.
+fn covered_return(total: f64, coverage: f64) -> String {
+    match coverage > 0f64 {
+        true => format!("{:.4}%", total / coverage * 100f64),
+        false => "-".to_string(),
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "Old return:         {} ({:.4}% of deals)", covered_return(self.old_return, self.old_coverage), self.old_coverage * 100f64)?;
+        writeln!(formatter, "New return:         {} ({:.4}% of deals)", covered_return(self.new_return, self.new_coverage), self.new_coverage * 100f64)?;
+        writeln!(formatter, "Compared deals:     {:.4}%", self.compared * 100f64)?;
+        writeln!(formatter, "Return change:      {:+.4}% (over the compared deals)", self.return_change() * 100f64)?;
+        writeln!(formatter, "Changed holds:      {} patterns ({:.4}% of deals)", self.changes.len(), self.changed_frequency() * 100f64)?;
+        writeln!(formatter)?;
+        writeln!(formatter, "{:>9} {:>12} {:>10} {:>13}  Hold", "Patterns", "Probability", "EV delta", "Contribution")?;
+
+        for class in &self.classes {
+            let hold = match class.hold_changed() {
+                true => format!("{} -> {}", class.old, class.new),
+                false => class.old.to_string(),
+            };
+
+            writeln!(
+                formatter, "{:>9} {:>11.6}% {:>+10.4} {:>+12.6}%  {hold}",
+                class.patterns, class.probability * 100f64, class.delta(), class.contribution * 100f64
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A hold class whose line differs between two exported charts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryChange {
+    pub hold: String,
+    /// `None` if the old chart has no line of the hold.
+    pub old: Option<ExportedEntry>,
+    /// `None` if the new chart has no line of the hold.
+    pub new: Option<ExportedEntry>,
+}
+
+impl EntryChange {
+    /// The change of the average expected value, if both charts have the hold.
+    pub fn delta(&self) -> Option<f64> {
+        Some(self.new.as_ref()?.average_score - self.old.as_ref()?.average_score)
+    }
+}
+
+/// The differences of a new exported chart to an old one, hold class by hold class.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartDiff {
+    /// Ordered by the new priority, followed by the holds missing from the new chart.
+    pub changes: Vec<EntryChange>,
+}
+
+impl ChartDiff {
+    /// Matches the lines of the charts by their hold, reporting the holds whose priority, amount of hands or
+    /// average expected value changed.
+    pub fn compare(old: &[ExportedEntry], new: &[ExportedEntry]) -> Self {
+        let find = |entries: &[ExportedEntry], hold: &str| entries.iter().find(|entry| entry.hold == hold).cloned();
+
+        let holds = new.iter().chain(old).map(|entry| entry.hold.as_str()).unique();
+
+        let changes = holds
+            .map(|hold| EntryChange { hold: hold.to_string(), old: find(old, hold), new: find(new, hold) })
+            .filter(|change| match (&change.old, &change.new) {
+                (Some(old), Some(new)) => old.priority != new.priority || old.patterns != new.patterns || change.delta().is_some_and(|delta| delta.abs() > TOLERANCE),
+                _ => true,
+            })
+            .collect();
+
+        Self { changes }
+    }
+}
+
+impl fmt::Display for ChartDiff {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "Changed holds:      {}", self.changes.len())?;
+        writeln!(formatter)?;
+        writeln!(formatter, "{:>9} {:>9} {:>9} {:>9} {:>10}  Hold", "Priority", "Old hands", "New hands", "New EV", "EV delta")?;
+
+        let field = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+
+        for change in &self.changes {
+            let priority = match (&change.old, &change.new) {
+                (Some(old), Some(new)) if old.priority != new.priority => format!("{} -> {}", old.priority, new.priority),
+                (_, Some(entry)) | (Some(entry), None) => entry.priority.to_string(),
+                (None, None) => "-".to_string(),
+            };
+
+            writeln!(
+                formatter, "{:>9} {:>9} {:>9} {:>9} {:>10}  {}",
+                priority,
+                field(change.old.as_ref().map(|entry| entry.patterns.to_string())),
+                field(change.new.as_ref().map(|entry| entry.patterns.to_string())),
+                field(change.new.as_ref().map(|entry| format!("{:.4}", entry.average_score))),
+                field(change.delta().map(|delta| format!("{delta:+.4}"))),
+                change.hold
+            )?;
+        }
+
+        Ok(())
+    }
+}
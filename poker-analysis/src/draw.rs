@@ -0,0 +1,235 @@
+//! Exact outcome distributions of holds.
+//!
+//! Instead of enumerating the draws of every hold, the outcome of every final hand is tallied once
+//! for each of its subsets. The final hands reachable from a hold are then obtained by inclusion-exclusion
+//! over the discarded cards, which turns the evaluation of a hold into at most 32 table lookups.
+
+use std::collections::{BTreeMap, HashMap};
+
+use itertools::Itertools;
+use poker_base::{Card, ComputedMove, GameVariant, Paytable};
+use poker_strategy::solver;
+
+/// The binomial coefficient.
+pub fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+
+    (0..k).fold(1, |result, index| result * (n - index) / (index + 1))
+}
+
+/// The distribution of the final hands when drawing to a hold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    /// The indices of the kept cards.
+    pub keep: Vec<usize>,
+    /// For each category of the paytable, the amount of draws ending in it. The last entry counts non-paying hands.
+    pub counts: Vec<u64>,
+}
+
+impl Outcome {
+    /// The amount of possible draws.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// The probability of each category (and, last, of a non-paying hand).
+    pub fn probabilities(&self) -> impl Iterator<Item = f64> + '_ {
+        let total = self.total() as f64;
+
+        self.counts.iter().map(move |&count| count as f64 / total)
+    }
+
+    /// The expected payout.
+    pub fn expected_value(&self, paytable: &Paytable) -> f64 {
+        self.probabilities()
+            .zip(&paytable.payouts)
+            .map(|(probability, payout)| probability * payout.payout as f64)
+            .sum()
+    }
+
+    /// The distribution of the payouts (payout: amount of draws).
+    pub fn distribution(&self, paytable: &Paytable) -> BTreeMap<usize, u64> {
+        let mut distribution = BTreeMap::new();
+
+        let payouts = paytable.payouts.iter().map(|payout| payout.payout).chain([0]);
+
+        for (payout, &count) in payouts.zip(&self.counts) {
+            if count != 0 {
+                *distribution.entry(payout).or_default() += count;
+            }
+        }
+
+        distribution
+    }
+
+    /// The expected squared payout.
+    pub fn second_moment(&self, paytable: &Paytable) -> f64 {
+        self.probabilities()
+            .zip(&paytable.payouts)
+            .map(|(probability, payout)| probability * (payout.payout * payout.payout) as f64)
+            .sum()
+    }
+}
+
+/// Holds the outcome counts of all subsets (of up to five cards) of a deck.
+pub struct DrawTable {
+    paytable: Paytable,
+    deck: Vec<Card>,
+    /// The wild cards of the game.
+    wilds: Vec<Card>,
+    indices: HashMap<Card, usize>,
+    /// `binomials[n][k]` for all `n` up to the deck size and `k` up to five.
+    binomials: Vec<[usize; 6]>,
+    /// Offset of the subsets of each size.
+    offsets: [usize; 6],
+    counts: Vec<u32>,
+}
+
+impl DrawTable {
+    /// Tallies every five card hand of the game's deck. This evaluates every hand once and may take a few seconds.
+    pub fn new(game: impl GameVariant, paytable: &Paytable) -> Self {
+        let deck = &game.deck();
+        let categories = paytable.payouts.len() + 1;
+
+        let mut offsets = [0usize; 6];
+        for size in 1..offsets.len() {
+            offsets[size] = offsets[size - 1] + binomial(deck.len(), size - 1);
+        }
+
+        let mut table = Self {
+            paytable: paytable.clone(),
+            deck: deck.to_vec(),
+            wilds: deck.iter().copied().filter(|&card| game.is_wild(card)).collect(),
+            indices: deck.iter().enumerate().map(|(index, &card)| (card, index)).collect(),
+            binomials: (0..=deck.len()).map(|n| [0, 1, 2, 3, 4, 5].map(|k| binomial(n, k))).collect(),
+            offsets,
+            counts: vec![0u32; (offsets[5] + binomial(deck.len(), 5)) * categories],
+        };
+
+        for hand in (0..deck.len()).combinations(5) {
+            let evaluation = game.evaluate(hand.iter().map(|&index| deck[index]).collect());
+            let category = paytable.category(evaluation).unwrap_or(categories - 1);
+
+            for mask in 0..(1 << hand.len()) {
+                let subset = hand.iter().enumerate().filter(|(position, _)| mask & (1 << position) != 0).map(|(_, &card)| card);
+                let index = table.index(subset);
+
+                table.counts[index * categories + category] += 1;
+            }
+        }
+
+        table
+    }
+
+    pub fn paytable(&self) -> &Paytable {
+        &self.paytable
+    }
+
+    pub fn deck(&self) -> &[Card] {
+        &self.deck
+    }
+
+    /// The wild cards of the game of the table.
+    pub fn wilds(&self) -> &[Card] {
+        &self.wilds
+    }
+
+    pub fn is_wild(&self, card: Card) -> bool {
+        self.wilds.contains(&card)
+    }
+
+    /// The position of a (sorted) subset of deck indices in the table, using the combinatorial number system.
+    fn index(&self, subset: impl Iterator<Item = usize>) -> usize {
+        let (size, index) = subset.fold((0, 0), |(size, index), card| (size + 1, index + self.binomials[card][size + 1]));
+
+        self.offsets[size] + index
+    }
+
+    /// The table indices of all 32 subsets (by bit mask over the positions) of the hand.
+    /// # Panics
+    /// if a card of the hand is not part of the deck.
+    fn subset_indices(&self, hand: &[Card; 5]) -> [usize; 32] {
+        let indices = hand.map(|card| *self.indices.get(&card).expect("card must be part of the deck"));
+
+        let mut positions = [0, 1, 2, 3, 4];
+        positions.sort_by_key(|&position| indices[position]);
+
+        let mut subsets = [0usize; 32];
+
+        for (mask, subset) in subsets.iter_mut().enumerate() {
+            *subset = self.index(positions.iter().filter(|&&position| mask & (1 << position) != 0).map(|&position| indices[position]));
+        }
+
+        subsets
+    }
+
+    /// Counts the draws to the hold given by the bit mask using inclusion-exclusion over the discarded cards.
+    fn counts(&self, subsets: &[usize; 32], keep: usize) -> Vec<u64> {
+        let categories = self.paytable.payouts.len() + 1;
+        let discarded = !keep & 0b11111;
+
+        let mut counts = vec![0i64; categories];
+
+        for mask in 0..32usize {
+            if mask & discarded != mask {
+                continue;
+            }
+
+            let sign = if mask.count_ones() % 2 == 0 { 1 } else { -1 };
+            let index = subsets[keep | mask] * categories;
+
+            for (category, count) in counts.iter_mut().enumerate() {
+                *count += sign * self.counts[index + category] as i64;
+            }
+        }
+
+        counts.into_iter().map(|count| count as u64).collect()
+    }
+
+    /// Computes the outcome distribution of keeping the given cards (indices into `hand`).
+    /// # Panics
+    /// if a card of the hand is not part of the deck.
+    pub fn outcome(&self, hand: &[Card; 5], keep: &[usize]) -> Outcome {
+        let subsets = self.subset_indices(hand);
+        let mask = keep.iter().fold(0, |mask, index| mask | (1 << index));
+
+        Outcome { keep: keep.to_vec(), counts: self.counts(&subsets, mask) }
+    }
+
+    /// Computes the outcome distributions of all 32 holds.
+    pub fn outcomes(&self, hand: &[Card; 5]) -> Vec<Outcome> {
+        let subsets = self.subset_indices(hand);
+
+        (0..hand.len())
+            .powerset()
+            .map(|keep| {
+                let mask = keep.iter().fold(0, |mask, index| mask | (1 << index));
+
+                Outcome { counts: self.counts(&subsets, mask), keep }
+            })
+            .collect()
+    }
+
+    /// Computes the optimal hold, i.e. the one with the highest expected payout. Of equally good holds, the
+    /// first by [solver::tie_break] is chosen, as by [poker_strategy::video_poker::calculate_optimal].
+    pub fn optimal(&self, hand: &[Card; 5]) -> ComputedMove {
+        let (outcome, average_score) = self.outcomes(hand)
+            .into_iter()
+            .map(|outcome| {
+                let score = outcome.expected_value(&self.paytable);
+
+                (outcome, score)
+            })
+            .max_by(|(a, a_score), (b, b_score)| a_score.total_cmp(b_score).then_with(|| solver::tie_break(&b.keep, &a.keep)))
+            .unwrap();
+
+        ComputedMove {
+            pattern: *hand,
+            distribution: outcome.distribution(&self.paytable),
+            keep: outcome.keep,
+            average_score,
+        }
+    }
+}
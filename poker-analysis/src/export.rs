@@ -0,0 +1,255 @@
+use core::fmt;
+use std::{collections::HashMap, fmt::Write, path::Path, str::FromStr};
+
+use itertools::Itertools;
+use poker_base::{Card, ComputedMove};
+
+use crate::hold::{self, HoldClass};
+
+/// The output format of an exported chart.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Format {
+    Markdown,
+    Html,
+    Csv,
+}
+
+impl Format {
+    /// Derives the format from the extension of the given file name.
+    pub fn from_extension(file: &Path) -> Option<Self> {
+        file.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format `{format}` (expected markdown, html or csv).")),
+        }
+    }
+}
+
+/// A line of a hold-priority chart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartEntry {
+    pub class: HoldClass,
+    /// The amount of patterns for which this hold is optimal.
+    pub patterns: usize,
+    pub average_score: f64,
+    pub min_score: f64,
+    pub max_score: f64,
+}
+
+/// A hold-priority chart: hold classes in the order they are to be held.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Chart {
+    pub entries: Vec<ChartEntry>,
+}
+
+impl Chart {
+    /// Groups the optimal holds into their pattern classes and orders the classes by hold priority: a class
+    /// comes before the classes it was held over, i.e. that were also available in hands it was optimal for.
+    /// Where the hands conflict, the class that the remaining classes were held over in the fewest hands comes first,
+    /// ties by the higher average score.
+    pub fn from_moves<'a>(moves: impl IntoIterator<Item = &'a ComputedMove>, is_wild: impl Fn(Card) -> bool + Copy) -> Self {
+        let mut classes: HashMap<HoldClass, ChartEntry> = HashMap::new();
+        // how many hands each class was held over each other class in.
+        let mut beats: HashMap<(HoldClass, HoldClass), usize> = HashMap::new();
+
+        for r#move in moves {
+            let class = hold::classify(&r#move.pattern, &r#move.keep, is_wild);
+
+            let entry = classes.entry(class.clone()).or_insert(ChartEntry {
+                class: class.clone(),
+                patterns: 0,
+                average_score: 0f64,
+                min_score: f64::INFINITY,
+                max_score: f64::NEG_INFINITY,
+            });
+
+            entry.patterns += 1;
+            // running sum; divided below
+            entry.average_score += r#move.average_score;
+            entry.min_score = entry.min_score.min(r#move.average_score);
+            entry.max_score = entry.max_score.max(r#move.average_score);
+
+            let others = (0..r#move.pattern.len()).powerset().map(|keep| hold::classify(&r#move.pattern, &keep, is_wild)).unique();
+
+            for other in others.filter(|other| *other != class) {
+                *beats.entry((class.clone(), other)).or_default() += 1;
+            }
+        }
+
+        let mut remaining: Vec<_> = classes
+            .into_values()
+            .map(|mut entry| {
+                entry.average_score /= entry.patterns as f64;
+                entry
+            })
+            .collect();
+
+        let beats = |a: &ChartEntry, b: &ChartEntry| beats.get(&(a.class.clone(), b.class.clone())).copied().unwrap_or(0);
+
+        // the hands each class would be misplayed in if it came first.
+        let mut beaten: Vec<usize> = remaining.iter().map(|entry| remaining.iter().map(|other| beats(other, entry)).sum()).collect();
+        let mut entries = Vec::with_capacity(remaining.len());
+
+        while !remaining.is_empty() {
+            let index = (0..remaining.len())
+                .min_by(|&a, &b| {
+                    beaten[a]
+                        .cmp(&beaten[b])
+                        .then_with(|| remaining[b].average_score.total_cmp(&remaining[a].average_score))
+                        .then_with(|| remaining[a].class.cmp(&remaining[b].class))
+                })
+                .unwrap();
+
+            let entry = remaining.swap_remove(index);
+            beaten.swap_remove(index);
+
+            for (other, beaten) in remaining.iter().zip(&mut beaten) {
+                *beaten -= beats(&entry, other);
+            }
+
+            entries.push(entry);
+        }
+
+        Self { entries }
+    }
+
+    /// Renders the chart in the given format.
+    pub fn render(&self, format: Format) -> Result<String, fmt::Error> {
+        let mut output = String::new();
+
+        match format {
+            Format::Markdown => {
+                writeln!(output, "| # | Hold | Hands | Average EV | Min EV | Max EV |")?;
+                writeln!(output, "|--:|------|------:|-----------:|-------:|-------:|")?;
+
+                for (index, entry) in self.entries.iter().enumerate() {
+                    writeln!(
+                        output, "| {} | {} | {} | {:.4} | {:.4} | {:.4} |",
+                        index + 1, entry.class, entry.patterns, entry.average_score, entry.min_score, entry.max_score
+                    )?;
+                }
+            },
+            Format::Html => {
+                writeln!(output, "<table>")?;
+                writeln!(output, "  <tr><th>#</th><th>Hold</th><th>Hands</th><th>Average EV</th><th>Min EV</th><th>Max EV</th></tr>")?;
+
+                for (index, entry) in self.entries.iter().enumerate() {
+                    writeln!(
+                        output, "  <tr><td>{}</td><td>{}</td><td>{}</td><td>{:.4}</td><td>{:.4}</td><td>{:.4}</td></tr>",
+                        index + 1, escape_html(&entry.class.to_string()), entry.patterns, entry.average_score, entry.min_score, entry.max_score
+                    )?;
+                }
+
+                writeln!(output, "</table>")?;
+            },
+            Format::Csv => {
+                writeln!(output, "priority,hold,hands,average_ev,min_ev,max_ev")?;
+
+                for (index, entry) in self.entries.iter().enumerate() {
+                    writeln!(
+                        output, "{},{},{},{},{},{}",
+                        index + 1, escape_csv(&entry.class.to_string()), entry.patterns, entry.average_score, entry.min_score, entry.max_score
+                    )?;
+                }
+            },
+        }
+
+        Ok(output)
+    }
+}
+
+/// A line of an exported chart, its hold named by the label it was exported with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportedEntry {
+    pub priority: usize,
+    pub hold: String,
+    pub patterns: usize,
+    pub average_score: f64,
+}
+
+/// Reads back the lines of a chart exported as Markdown or CSV. HTML charts are only meant to be read by players.
+pub fn parse(export: &str, format: Format) -> Result<Vec<ExportedEntry>, String> {
+    let header = match format {
+        Format::Markdown => 2,
+        Format::Csv => 1,
+        Format::Html => return Err("HTML charts cannot be read back (expected .md or .csv).".to_string()),
+    };
+
+    export
+        .lines()
+        .enumerate()
+        .skip(header)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let fields = match format {
+                Format::Csv => split_csv(line)?,
+                _ => split_markdown(line),
+            };
+
+            let [priority, hold, patterns, average_score, _, _] = <[String; 6]>::try_from(fields)
+                .map_err(|fields| format!("line {}: expected 6 fields, found {}", index + 1, fields.len()))?;
+
+            let number = |field: &str| format!("line {}: invalid {field}", index + 1);
+
+            Ok(ExportedEntry {
+                priority: priority.parse().map_err(|_| number("priority"))?,
+                hold,
+                patterns: patterns.parse().map_err(|_| number("amount of hands"))?,
+                average_score: average_score.parse().map_err(|_| number("average EV"))?,
+            })
+        })
+        .collect()
+}
+
+fn split_markdown(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+
+    line.split('|').map(|field| field.trim().to_string()).collect()
+}
+
+/// Splits a line of CSV as written by [escape_csv].
+fn split_csv(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut characters = line.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match (character, quoted) {
+            ('"', true) if characters.peek() == Some(&'"') => {
+                characters.next();
+                fields.last_mut().unwrap().push('"');
+            },
+            ('"', _) => quoted = !quoted,
+            (',', false) => fields.push(String::new()),
+            (character, _) => fields.last_mut().unwrap().push(character),
+        }
+    }
+
+    match quoted {
+        true => Err(format!("unterminated quote in `{line}`")),
+        false => Ok(fields),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn escape_csv(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
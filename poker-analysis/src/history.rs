@@ -0,0 +1,262 @@
+//! Hand histories of real sessions and the audit of the player's decisions.
+//!
+//! A hand history lists one played hand per line: the dealt hand, the held cards (`-` for none), the
+//! final hand and the observed payout per coin, separated by `|`, e.g.
+//!
+//! ```text
+//! # dealt          | held  | final          | payout
+//! Ah Kh 7c 4d 2s   | Ah Kh | Ah Kh Qd 9c 3s | 0
+//! ```
+//!
+//! Empty lines and `#` comments are skipped.
+
+use core::fmt;
+use std::{collections::HashMap, error::Error, str::FromStr};
+
+use poker_base::{parse_deck_cards, parse_deck_hand, Card, Deck, GameVariant};
+
+use crate::{draw::DrawTable, hold::{self, HoldClass}, policy::Policy};
+
+/// Two holds whose expected values differ by less count as equally good.
+const TOLERANCE: f64 = 1e-9;
+
+/// A played hand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// The line of the hand history, starting at one.
+    pub line: usize,
+    pub dealt: [Card; 5],
+    /// The indices of the held cards of the dealt hand, ascending.
+    pub keep: Vec<usize>,
+    pub drawn: [Card; 5],
+    /// The observed payout per coin bet.
+    pub payout: usize,
+}
+
+/// An invalid line of a hand history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryError {
+    /// The line, starting at one.
+    pub line: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "invalid hand on line {}: {}", self.line, self.reason)
+    }
+}
+
+impl Error for HistoryError {}
+
+/// Parses a played hand of the given deck, checking that the held cards were dealt and are part of the final
+/// hand, and that the discarded cards were not drawn again.
+fn parse_record(line: usize, record: &str, deck: &[Card]) -> Result<Record, String> {
+    let [dealt, held, drawn, payout] = record.split('|').map(str::trim).collect::<Vec<_>>()
+        .try_into()
+        .map_err(|_| "expected the dealt hand, the held cards, the final hand and the payout".to_string())?;
+
+    let dealt = parse_deck_hand(dealt, deck).map_err(|error| format!("dealt hand: {error}"))?;
+    let drawn = parse_deck_hand(drawn, deck).map_err(|error| format!("final hand: {error}"))?;
+    let held = match held {
+        "-" => Vec::new(),
+        held => parse_deck_cards(held, deck).map_err(|error| format!("held cards: {error}"))?,
+    };
+    let payout = payout.parse().map_err(|_| format!("`{payout}` is not a payout"))?;
+
+    let mut keep = held
+        .iter()
+        .map(|card| dealt.iter().position(|dealt| dealt == card).ok_or(format!("the held {card} was not dealt")))
+        .collect::<Result<Vec<usize>, String>>()?;
+    keep.sort_unstable();
+    keep.dedup();
+
+    if let Some(card) = held.iter().find(|card| !drawn.contains(card)) {
+        return Err(format!("the held {card} is missing from the final hand"));
+    }
+
+    if let Some(card) = drawn.iter().find(|card| !held.contains(card) && dealt.contains(card)) {
+        return Err(format!("the discarded {card} was drawn again"));
+    }
+
+    Ok(Record { line, dealt, keep, drawn, payout })
+}
+
+/// The played hands of a hand history.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct History {
+    pub records: Vec<Record>,
+}
+
+impl History {
+    /// Parses the hands of a game dealt from the given deck (e.g. [GameVariant::deck]), so that cards the
+    /// game does not deal, such as a joker outside of Joker Poker, are rejected.
+    pub fn parse(history: &str, deck: &[Card]) -> Result<Self, HistoryError> {
+        let mut records = Vec::new();
+
+        for (index, line) in history.lines().enumerate() {
+            let record = line.split('#').next().unwrap().trim();
+
+            if record.is_empty() {
+                continue;
+            }
+
+            records.push(parse_record(index + 1, record, deck).map_err(|reason| HistoryError { line: index + 1, reason })?);
+        }
+
+        Ok(Self { records })
+    }
+}
+
+impl FromStr for History {
+    type Err = HistoryError;
+
+    /// Parses the hands of a game dealt from the standard deck.
+    fn from_str(history: &str) -> Result<Self, Self::Err> {
+        Self::parse(history, &Deck::standard().cards)
+    }
+}
+
+/// A hand the player held worse than the optimal hold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mistake {
+    pub line: usize,
+    pub dealt: [Card; 5],
+    pub keep: Vec<usize>,
+    pub optimal: Vec<usize>,
+    /// The expected value lost by the hold.
+    pub loss: f64,
+}
+
+/// Mistakes whose held and optimal holds fall into the same pattern classes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MistakeClass {
+    pub held: HoldClass,
+    pub optimal: HoldClass,
+    pub count: usize,
+    pub loss: f64,
+}
+
+/// A hand paid differently than the paytable pays its final hand.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoutMismatch {
+    pub line: usize,
+    pub drawn: [Card; 5],
+    pub observed: usize,
+    pub expected: usize,
+}
+
+/// The audit of a hand history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audit {
+    pub hands: usize,
+    /// The summed observed payouts.
+    pub payout: usize,
+    /// The summed expected values of the played holds.
+    pub played_value: f64,
+    /// The summed expected values of the optimal holds.
+    pub optimal_value: f64,
+    /// In the order of the hand history.
+    pub mistakes: Vec<Mistake>,
+    /// Ordered from the most to the least common.
+    pub mistake_classes: Vec<MistakeClass>,
+    pub mismatches: Vec<PayoutMismatch>,
+}
+
+impl Audit {
+    /// Compares every decision of the history to the optimal policy (e.g. [crate::policy::Optimal]) and
+    /// checks every payout against the table's paytable.
+    pub fn new(game: &impl GameVariant, table: &DrawTable, history: &History, optimal: &impl Policy) -> Self {
+        let paytable = table.paytable();
+
+        let mut audit = Audit {
+            hands: history.records.len(),
+            payout: 0,
+            played_value: 0f64,
+            optimal_value: 0f64,
+            mistakes: Vec::new(),
+            mistake_classes: Vec::new(),
+            mismatches: Vec::new(),
+        };
+
+        let mut classes: HashMap<(HoldClass, HoldClass), MistakeClass> = HashMap::new();
+
+        for record in &history.records {
+            let optimal = optimal.hold(&record.dealt);
+            let optimal_value = table.outcome(&record.dealt, &optimal).expected_value(paytable);
+            let value = match record.keep == optimal {
+                true => optimal_value,
+                false => table.outcome(&record.dealt, &record.keep).expected_value(paytable),
+            };
+
+            audit.payout += record.payout;
+            audit.played_value += value;
+            audit.optimal_value += optimal_value;
+
+            if optimal_value - value > TOLERANCE {
+                let (held_class, optimal_class) = (hold::classify(&record.dealt, &record.keep, |card| game.is_wild(card)), hold::classify(&record.dealt, &optimal, |card| game.is_wild(card)));
+
+                let class = classes.entry((held_class.clone(), optimal_class.clone())).or_insert(MistakeClass {
+                    held: held_class,
+                    optimal: optimal_class,
+                    count: 0,
+                    loss: 0f64,
+                });
+
+                class.count += 1;
+                class.loss += optimal_value - value;
+
+                audit.mistakes.push(Mistake {
+                    line: record.line,
+                    dealt: record.dealt,
+                    keep: record.keep.clone(),
+                    optimal,
+                    loss: optimal_value - value,
+                });
+            }
+
+            let expected = paytable.score(game.evaluate(record.drawn.to_vec()));
+
+            if expected != record.payout {
+                audit.mismatches.push(PayoutMismatch { line: record.line, drawn: record.drawn, observed: record.payout, expected });
+            }
+        }
+
+        audit.mistake_classes = classes.into_values().collect();
+        audit.mistake_classes.sort_by(|a, b| b.count.cmp(&a.count).then(b.loss.total_cmp(&a.loss)).then_with(|| (&a.held, &a.optimal).cmp(&(&b.held, &b.optimal))));
+
+        audit
+    }
+
+    /// The expected value lost by all mistakes.
+    pub fn lost(&self) -> f64 {
+        self.optimal_value - self.played_value
+    }
+
+    /// The share of the hands held optimally.
+    pub fn accuracy(&self) -> f64 {
+        1f64 - self.mistakes.len() as f64 / self.hands.max(1) as f64
+    }
+}
+
+impl fmt::Display for Audit {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hands = self.hands.max(1) as f64;
+
+        writeln!(formatter, "Hands:              {}", self.hands)?;
+        writeln!(formatter, "Accuracy:           {:.2}% ({} mistakes)", self.accuracy() * 100f64, self.mistakes.len())?;
+        writeln!(formatter, "EV lost:            {:.4} coins ({:.4}% of the bet)", self.lost(), self.lost() / hands * 100f64)?;
+        writeln!(formatter, "Optimal return:     {:.4}%", self.optimal_value / hands * 100f64)?;
+        writeln!(formatter, "Played return:      {:.4}%", self.played_value / hands * 100f64)?;
+        writeln!(formatter, "Observed return:    {:.4}%", self.payout as f64 / hands * 100f64)?;
+        writeln!(formatter, "Payout mismatches:  {}", self.mismatches.len())?;
+        writeln!(formatter)?;
+        writeln!(formatter, "{:>6} {:>10}  Mistake", "Count", "EV lost")?;
+
+        for class in &self.mistake_classes {
+            writeln!(formatter, "{:>6} {:>10.4}  {} instead of {}", class.count, class.loss, class.held, class.optimal)?;
+        }
+
+        Ok(())
+    }
+}
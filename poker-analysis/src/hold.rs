@@ -0,0 +1,302 @@
+use core::fmt;
+
+use itertools::Itertools;
+use poker_base::{Card, Rank, Suit, Value};
+
+/// A hand that already pays (or is a part of one) and is kept as such.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum MadeHand {
+    LowPair,
+    HighPair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    RoyalFlush,
+    FiveOfAKind,
+}
+
+impl MadeHand {
+    pub const ALL: [MadeHand; 11] = [
+        MadeHand::LowPair, MadeHand::HighPair, MadeHand::TwoPair, MadeHand::ThreeOfAKind, MadeHand::Straight, MadeHand::Flush,
+        MadeHand::FullHouse, MadeHand::FourOfAKind, MadeHand::StraightFlush, MadeHand::RoyalFlush, MadeHand::FiveOfAKind,
+    ];
+}
+
+impl fmt::Display for MadeHand {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MadeHand::LowPair => "Low pair",
+            MadeHand::HighPair => "High pair",
+            MadeHand::TwoPair => "Two pair",
+            MadeHand::ThreeOfAKind => "Three of a kind",
+            MadeHand::Straight => "Straight",
+            MadeHand::Flush => "Flush",
+            MadeHand::FullHouse => "Full house",
+            MadeHand::FourOfAKind => "Four of a kind",
+            MadeHand::StraightFlush => "Straight flush",
+            MadeHand::RoyalFlush => "Royal flush",
+            MadeHand::FiveOfAKind => "Five of a kind",
+        };
+
+        write!(formatter, "{name}")
+    }
+}
+
+/// The structural kind of a hold.
+#[derive(PartialEq, Eq, Debug, Clone, Hash, PartialOrd, Ord)]
+pub enum HoldKind {
+    Made(MadeHand),
+    /// Suited cards of ten or higher.
+    RoyalFlushDraw { cards: usize },
+    /// Suited cards that fit into a straight, with the [gaps] of the straight.
+    StraightFlushDraw { cards: usize, gaps: usize, high_cards: usize },
+    FlushDraw { cards: usize, high_cards: usize },
+    /// An outside straight draw can be completed on both ends.
+    StraightDraw { cards: usize, outside: bool, high_cards: usize },
+    /// High cards (jack or better) that are not part of any other draw.
+    HighCards { values: Vec<Value>, suited: bool },
+    /// Anything else, e.g. a lone low card.
+    Other { values: Vec<Value>, suited: bool },
+    DiscardAll,
+}
+
+impl fmt::Display for HoldKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn values(values: &[Value]) -> String {
+            values.iter().map(Value::to_string).collect()
+        }
+
+        fn high_cards(high_cards: usize) -> String {
+            match high_cards {
+                0 => String::new(),
+                1 => " (1 high card)".to_string(),
+                high_cards => format!(" ({high_cards} high cards)"),
+            }
+        }
+
+        match self {
+            HoldKind::Made(made) => write!(formatter, "{made}"),
+            HoldKind::RoyalFlushDraw { cards } => write!(formatter, "{cards} to a royal flush"),
+            HoldKind::StraightFlushDraw { cards, gaps, high_cards: high } => {
+                let gaps = match gaps {
+                    0 => "open".to_string(),
+                    1 => "1 gap".to_string(),
+                    gaps => format!("{gaps} gaps"),
+                };
+
+                write!(formatter, "{cards} to a straight flush, {gaps}{}", high_cards(*high))
+            },
+            HoldKind::FlushDraw { cards, high_cards: high } => write!(formatter, "{cards} to a flush{}", high_cards(*high)),
+            HoldKind::StraightDraw { cards, outside, high_cards: high } => {
+                let kind = if *outside { "an outside" } else { "an inside" };
+
+                write!(formatter, "{cards} to {kind} straight{}", high_cards(*high))
+            },
+            HoldKind::HighCards { values: kept, .. } if kept.len() == 1 => write!(formatter, "Single {}", values(kept)),
+            HoldKind::HighCards { values: kept, suited: true } => write!(formatter, "Suited {}", values(kept)),
+            HoldKind::HighCards { values: kept, suited: false } => write!(formatter, "Unsuited {}", values(kept)),
+            HoldKind::Other { values: kept, suited: true } if kept.len() > 1 => write!(formatter, "Other: suited {}", values(kept)),
+            HoldKind::Other { values: kept, .. } => write!(formatter, "Other: {}", values(kept)),
+            HoldKind::DiscardAll => write!(formatter, "Discard everything"),
+        }
+    }
+}
+
+/// Discarded cards that lower the chances of the kept cards.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, PartialOrd, Ord, Default)]
+pub struct Penalties {
+    /// A card of the kept suit is discarded.
+    pub flush: bool,
+    /// A card that could complete a straight with the kept cards is discarded.
+    pub straight: bool,
+}
+
+/// The pattern class of a hold, used to group holds of different hands.
+#[derive(PartialEq, Eq, Debug, Clone, Hash, PartialOrd, Ord)]
+pub struct HoldClass {
+    /// Made hands count the kept wild cards in, draws are those of the kept natural cards.
+    pub kind: HoldKind,
+    pub penalties: Penalties,
+    /// The amount of kept wild cards.
+    pub wilds: usize,
+}
+
+impl fmt::Display for HoldClass {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wilds = match self.wilds {
+            1 => "1 wild card".to_string(),
+            wilds => format!("{wilds} wild cards"),
+        };
+
+        match (&self.kind, self.wilds) {
+            (kind, 0) => write!(formatter, "{kind}")?,
+            (HoldKind::DiscardAll, _) => write!(formatter, "{wilds}")?,
+            (kind, _) => {
+                let kind = kind.to_string();
+
+                write!(formatter, "{wilds} + {}{}", kind[..1].to_lowercase(), &kind[1..])?;
+            },
+        }
+
+        match (self.penalties.flush, self.penalties.straight) {
+            (true, true) => write!(formatter, " with flush and straight penalty"),
+            (true, false) => write!(formatter, " with flush penalty"),
+            (false, true) => write!(formatter, " with straight penalty"),
+            (false, false) => Ok(()),
+        }
+    }
+}
+
+/// Whether the value counts as a high card (jack or better).
+pub fn is_high(value: Value) -> bool {
+    value >= Value::Jack
+}
+
+/// Returns the ranks (ace high) of the values, or `None` if they do not fit into a straight.
+/// An ace is treated as low if that is the only way to fit.
+pub(crate) fn straight_ranks(values: &[Value]) -> Option<Vec<i8>> {
+    let high: Vec<i8> = values.iter().map(|&value| value as i8).collect();
+    let low: Vec<i8> = high.iter().map(|&rank| if rank == Value::Ace as i8 { -1 } else { rank }).collect();
+
+    [high, low].into_iter().find(|ranks| {
+        let (min, max) = (ranks.iter().min().unwrap(), ranks.iter().max().unwrap());
+
+        ranks.iter().all_unique() && max - min < 5
+    })
+}
+
+/// The values missing inside the straight ranks, a draw that can only be completed at one end (e.g. A234)
+/// counting one more, as it has as few ways to complete as an inside draw.
+pub(crate) fn gaps(ranks: &[i8]) -> usize {
+    let (min, max) = (*ranks.iter().min().unwrap(), *ranks.iter().max().unwrap());
+    let inside = (max - min + 1) as usize - ranks.len();
+    let one_ended = min == -1 || max == Value::Ace as i8;
+
+    inside + usize::from(one_ended)
+}
+
+/// Whether the straight ranks are consecutive and can be extended on both ends.
+pub(crate) fn is_outside(ranks: &[i8]) -> bool {
+    gaps(ranks) == 0
+}
+
+/// Classifies the hold of the `kept` cards (indices into `pattern`), the wild cards of the game (see
+/// [GameVariant::is_wild](poker_base::GameVariant::is_wild)) standing in for any card.
+pub fn classify(pattern: &[Card; 5], kept: &[usize], is_wild: impl Fn(Card) -> bool) -> HoldClass {
+    let cards: Vec<Card> = kept.iter().map(|&index| pattern[index]).collect();
+    let discarded: Vec<Card> = (0..pattern.len()).filter(|index| !kept.contains(index)).map(|index| pattern[index]).collect();
+
+    let (wilds, naturals): (Vec<Card>, Vec<Card>) = cards.iter().partition(|&&card| is_wild(card));
+
+    let kind = match cards.len() {
+        5 => made(&cards, is_wild),
+        _ => kind(&naturals, wilds.len()),
+    };
+
+    let penalties = match kind {
+        HoldKind::Made(_) | HoldKind::DiscardAll => Penalties::default(),
+        _ => penalties(&naturals, &discarded),
+    };
+
+    HoldClass { kind, penalties, wilds: wilds.len() }
+}
+
+/// The kind of a hold of all five cards.
+fn made(cards: &[Card], is_wild: impl Fn(Card) -> bool) -> HoldKind {
+    let made = match poker_base::compute_wild_rank(cards.to_vec(), is_wild).rank {
+        Rank::RoyalFlush(_) => MadeHand::RoyalFlush,
+        Rank::FiveOfAKind(_) => MadeHand::FiveOfAKind,
+        Rank::StraightFlush(_) => MadeHand::StraightFlush,
+        Rank::FourOfAKind { .. } => MadeHand::FourOfAKind,
+        Rank::FullHouse { .. } => MadeHand::FullHouse,
+        Rank::Flush(_) => MadeHand::Flush,
+        Rank::Straight { .. } => MadeHand::Straight,
+        Rank::ThreeOfAKind(_) => MadeHand::ThreeOfAKind,
+        Rank::TwoPair { .. } => MadeHand::TwoPair,
+        Rank::Pair(value) if is_high(value) => MadeHand::HighPair,
+        Rank::Pair(_) => MadeHand::LowPair,
+        Rank::HighCard(_) => return HoldKind::Other { values: values(cards), suited: is_suited(cards) },
+    };
+
+    HoldKind::Made(made)
+}
+
+/// The kind of a hold of fewer than five cards: the kept wild cards only complete sets of three or more.
+fn kind(cards: &[Card], wilds: usize) -> HoldKind {
+    if cards.is_empty() {
+        return HoldKind::DiscardAll;
+    }
+
+    let counts = cards.iter().counts_by(|card| card.value);
+    let mut kinds: Vec<usize> = counts.values().copied().collect();
+    kinds.sort_unstable();
+    kinds.reverse();
+    kinds[0] += wilds;
+
+    match kinds.as_slice() {
+        [count, ..] if *count >= 4 => return HoldKind::Made(MadeHand::FourOfAKind),
+        [3, ..] => return HoldKind::Made(MadeHand::ThreeOfAKind),
+        [2, 2, ..] => return HoldKind::Made(MadeHand::TwoPair),
+        [2, ..] if wilds > 0 => {},
+        [2, ..] => {
+            let (&value, _) = counts.iter().find(|(_, &count)| count == 2).unwrap();
+
+            return HoldKind::Made(if is_high(value) { MadeHand::HighPair } else { MadeHand::LowPair });
+        },
+        _ => {}
+    }
+
+    let kept = values(cards);
+    let suited = is_suited(cards);
+    let high_cards = kept.iter().filter(|&&value| is_high(value)).count();
+
+    if suited && cards.len() >= 2 && kept.iter().all(|&value| value >= Value::Ten) {
+        return HoldKind::RoyalFlushDraw { cards: cards.len() };
+    }
+
+    let straight = straight_ranks(&kept);
+
+    if let (true, true, Some(ranks)) = (suited, cards.len() >= 3, &straight) {
+        return HoldKind::StraightFlushDraw { cards: cards.len(), gaps: gaps(ranks), high_cards };
+    }
+
+    if suited && cards.len() >= 3 {
+        return HoldKind::FlushDraw { cards: cards.len(), high_cards };
+    }
+
+    if let (4, Some(ranks)) = (cards.len(), &straight) {
+        return HoldKind::StraightDraw { cards: cards.len(), outside: is_outside(ranks), high_cards };
+    }
+
+    if high_cards == kept.len() {
+        return HoldKind::HighCards { values: kept, suited };
+    }
+
+    HoldKind::Other { values: kept, suited }
+}
+
+fn penalties(cards: &[Card], discarded: &[Card]) -> Penalties {
+    let suit: Option<Suit> = if is_suited(cards) { Some(cards[0].suit) } else { None };
+
+    let flush = suit.is_some_and(|suit| discarded.iter().any(|card| card.suit == suit));
+
+    let kept = values(cards);
+    let straight = straight_ranks(&kept).is_some() && discarded.iter().any(|card| {
+        !kept.contains(&card.value) && straight_ranks(&[kept.as_slice(), &[card.value]].concat()).is_some()
+    });
+
+    Penalties { flush, straight }
+}
+
+pub(crate) fn is_suited(cards: &[Card]) -> bool {
+    cards.iter().map(|card| card.suit).all_equal()
+}
+
+/// The distinct values of the cards, highest first.
+fn values(cards: &[Card]) -> Vec<Value> {
+    cards.iter().map(|card| card.value).sorted().rev().dedup().collect()
+}
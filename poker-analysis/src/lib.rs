@@ -0,0 +1,18 @@
+//! Analysis of the strategy computed by the farm.
+
+pub mod cost;
+pub mod diff;
+pub mod draw;
+pub mod export;
+pub mod history;
+pub mod hold;
+pub mod multiplay;
+pub mod policy;
+pub mod priority;
+pub mod progressive;
+pub mod report;
+pub mod risk;
+pub mod simulate;
+pub mod state;
+pub mod strategy;
+pub mod synthesis;
@@ -0,0 +1,388 @@
+use std::{env, error::Error, fs, path::Path, time::Instant};
+
+use poker_analysis::{
+    cost::Cost, diff::{ChartDiff, Diff}, draw::DrawTable, export::{self, Chart, Format}, history::{Audit, History}, multiplay::{Moments, MultiPlayReport}, policy::{Computed, Optimal, Policy, Priorities}, priority::PriorityList, progressive::Progressive, report::Report,
+    risk::{PayoutDistribution, RiskReport}, simulate::{self, Settings}, state, strategy::Strategy, synthesis,
+};
+use poker_base::{game, parse_deck_cards, parse_deck_hand, Card, GameVariant, Paytable, Variant};
+use poker_strategy::video_poker;
+
+fn draw_table(variant: Variant, paytable: &Paytable) -> DrawTable {
+    log::info!("Tabulating final hands of `{variant}` for paytable `{}`...", paytable.name);
+
+    let start = Instant::now();
+    let table = DrawTable::new(variant, paytable);
+
+    log::info!("Tabulated final hands in {}ms.", start.elapsed().as_millis());
+
+    table
+}
+
+fn export(file: &str, output: &str) -> Result<(), Box<dyn Error>> {
+    let output = Path::new(output);
+    let format = Format::from_extension(output).ok_or("Unknown output format (expected .md, .html or .csv).")?;
+
+    log::info!("Loading computed moves from `{file}`...");
+    let state = state::load(file)?;
+    log::info!("Loaded {} computed moves of `{}`.", state.computed.len(), state.variant);
+
+    let chart = Chart::from_moves(&state.computed, |card| state.variant.is_wild(card));
+
+    fs::write(output, chart.render(format)?)?;
+
+    log::info!("Exported chart of {} hold classes to `{}`.", chart.entries.len(), output.display());
+
+    Ok(())
+}
+
+fn report(game_name: &str, file: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let (variant, paytable) = game::resolve(game_name)?;
+    let table = draw_table(variant, &paytable);
+
+    let report = match file {
+        Some(file) => Report::from_moves(&table, &state::load_variant_moves(file, variant)?),
+        None => {
+            log::info!("Solving all deals...");
+
+            Report::solve(&table)
+        },
+    };
+
+    println!("{report}");
+
+    Ok(())
+}
+
+fn risk(bankroll: &str, games: &str, game_name: &str, file: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let bankroll: usize = bankroll.parse()?;
+    let games: usize = games.parse()?;
+
+    let (variant, paytable) = game::resolve(game_name)?;
+    let table = draw_table(variant, &paytable);
+
+    let distribution = match file {
+        Some(file) => PayoutDistribution::from_moves(&table, &state::load_variant_moves(file, variant)?),
+        None => {
+            log::info!("Solving all deals...");
+
+            PayoutDistribution::from_report(&Report::solve(&table))
+        },
+    };
+
+    println!("{}", RiskReport::new(&distribution, bankroll, games));
+
+    Ok(())
+}
+
+/// The amount of points of the simulated bankroll trajectory.
+const TRAJECTORY_POINTS: usize = 20;
+
+fn run_simulation(table: &DrawTable, variant: Variant, policy: &impl Policy, settings: Settings) {
+    log::info!("Computing the analytical return...");
+    let report = Report::from_policy(table, policy);
+
+    log::info!("Simulating {} hands...", settings.hands);
+    let start = Instant::now();
+    let simulation = simulate::simulate(&variant, table.paytable(), policy, settings);
+    log::info!("Simulated {} hands in {}ms.", simulation.hands, start.elapsed().as_millis());
+
+    println!("{simulation}");
+    println!("Analytical return:  {:.4}%", report.expected_return * 100f64);
+    println!("Deviation:          {:.2} standard errors", simulation.deviation(report.expected_return));
+}
+
+fn simulate(hands: &str, seed: &str, game_name: &str, strategy: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let (hands, lines) = hands.split_once('x').unwrap_or((hands, "1"));
+    let settings = Settings { hands: hands.parse()?, lines: lines.parse()?, seed: seed.parse()?, points: TRAJECTORY_POINTS };
+
+    let (variant, paytable) = game::resolve(game_name)?;
+    let table = draw_table(variant, &paytable);
+
+    match strategy.map(|strategy| strategy.strip_prefix("chart:").ok_or(strategy)) {
+        None => run_simulation(&table, variant, &Optimal(&table), settings),
+        Some(Ok(file)) => {
+            let chart = Chart::from_moves(&state::load_variant_moves(file, variant)?, |card| variant.is_wild(card));
+
+            run_simulation(&table, variant, &Priorities::from_chart(&chart, table.wilds()), settings);
+        },
+        Some(Err(file)) => {
+            let strategy = Strategy::new(state::load_variant_moves(file, variant)?);
+
+            run_simulation(&table, variant, &Computed { strategy: &strategy, table: &table }, settings);
+        },
+    }
+
+    Ok(())
+}
+
+/// The amount of most expensive deviations, hold changes or mistakes listed.
+const WORST_HANDS: usize = 25;
+
+/// The held cards, e.g. `A♥ K♥`, or `-` for none.
+fn held(hand: &[Card; 5], keep: &[usize]) -> String {
+    match keep.is_empty() {
+        true => "-".to_string(),
+        false => keep.iter().map(|&index| hand[index].to_string()).collect::<Vec<_>>().join(" "),
+    }
+}
+
+fn chart(file: &str, game_name: &str, state_file: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let list: PriorityList = fs::read_to_string(file)?.parse()?;
+    log::info!("Loaded {} rules from `{file}`.", list.rules.len());
+
+    let (variant, paytable) = game::resolve(game_name)?;
+    let table = draw_table(variant, &paytable);
+
+    log::info!("Playing all deals by the priority list...");
+
+    let is_wild = |card| variant.is_wild(card);
+
+    let cost = match state_file {
+        Some(state_file) => {
+            let strategy = Strategy::new(state::load_variant_moves(state_file, variant)?);
+
+            Cost::measure(&table, &list.policy(is_wild), &Computed { strategy: &strategy, table: &table })
+        },
+        None => Cost::measure(&table, &list.policy(is_wild), &Optimal(&table)),
+    };
+
+    println!("{cost}");
+    println!("{:<16} {:<16} {:<16} {:>9} {:>10}  Rule", "Hand", "Held", "Optimal", "EV lost", "Cost");
+
+    for deviation in cost.deviations.iter().take(WORST_HANDS) {
+        let hand = held(&deviation.hand, &[0, 1, 2, 3, 4]);
+        let rule = match list.choose(&deviation.hand, is_wild) {
+            Some((index, _)) => format!("{}. {}", index + 1, list.rules[index]),
+            None => "(none)".to_string(),
+        };
+
+        println!(
+            "{hand:<16} {:<16} {:<16} {:>9.4} {:>9.5}%  {rule}",
+            held(&deviation.hand, &deviation.keep), held(&deviation.hand, &deviation.optimal), deviation.loss, deviation.cost() * 100f64
+        );
+    }
+
+    Ok(())
+}
+
+fn synthesize(lines: &str, output: &str, game_name: &str, state_file: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let lines: usize = lines.parse()?;
+
+    let (variant, paytable) = game::resolve(game_name)?;
+    let table = draw_table(variant, &paytable);
+
+    let synthesis = match state_file {
+        Some(state_file) => {
+            let strategy = Strategy::new(state::load_variant_moves(state_file, variant)?);
+
+            synthesis::synthesize(&table, &Computed { strategy: &strategy, table: &table }, lines)?
+        },
+        None => synthesis::synthesize(&table, &Optimal(&table), lines)?,
+    };
+
+    fs::write(output, format!("# {} lines for `{}`, returning {:.4}%.\n{}", synthesis.list.rules.len(), paytable.name, synthesis.list_return * 100f64, synthesis.list))?;
+
+    log::info!("Wrote the priority list to `{output}`.");
+
+    println!("{}", synthesis.list);
+    println!("Optimal return:     {:.4}%", synthesis.optimal_return * 100f64);
+    println!("List return:        {:.4}%", synthesis.list_return * 100f64);
+    println!("Return lost:        {:.4}%", (synthesis.optimal_return - synthesis.list_return) * 100f64);
+
+    Ok(())
+}
+
+fn multiplay(deals: &str, game_name: &str, state_file: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let deals: usize = deals.parse()?;
+
+    let (variant, paytable) = game::resolve(game_name)?;
+    let table = draw_table(variant, &paytable);
+
+    log::info!("Computing the payout moments of all deals...");
+
+    let moments = match state_file {
+        Some(state_file) => {
+            let strategy = Strategy::new(state::load_variant_moves(state_file, variant)?);
+
+            Moments::from_policy(&table, &Computed { strategy: &strategy, table: &table })
+        },
+        None => Moments::from_policy(&table, &Optimal(&table)),
+    };
+
+    println!("{}", MultiPlayReport::new(moments, deals));
+
+    Ok(())
+}
+
+fn progressive(max_royal: &str, game_name: &str) -> Result<(), Box<dyn Error>> {
+    let max_royal: f64 = max_royal.parse()?;
+
+    let (variant, paytable) = game::resolve(game_name)?;
+    let table = draw_table(variant, &paytable);
+
+    log::info!("Following the optimal holds of all deals up to a royal flush paying {max_royal}...");
+
+    let progressive = Progressive::analyze(&table, max_royal).ok_or("The paytable has no royal flush.")?;
+
+    println!("{progressive}");
+
+    Ok(())
+}
+
+fn diff(old_file: &str, new_file: &str) -> Result<(), Box<dyn Error>> {
+    match (Format::from_extension(Path::new(old_file)), Format::from_extension(Path::new(new_file))) {
+        (None, None) => {},
+        (Some(old_format), Some(new_format)) => {
+            let old = export::parse(&fs::read_to_string(old_file)?, old_format).map_err(|error| format!("`{old_file}`: {error}"))?;
+            let new = export::parse(&fs::read_to_string(new_file)?, new_format).map_err(|error| format!("`{new_file}`: {error}"))?;
+
+            println!("{}", ChartDiff::compare(&old, &new));
+
+            return Ok(());
+        },
+        _ => return Err("Compare either two state files or two exported charts.".into()),
+    }
+
+    let (old, new) = (state::load(old_file)?, state::load(new_file)?);
+
+    log::info!("Loaded {} old and {} new computed moves of `{}`.", old.computed.len(), new.computed.len(), old.variant);
+
+    if old.variant != new.variant {
+        return Err(format!("The state files compute `{}` and `{}`.", old.variant, new.variant).into());
+    }
+
+    let diff = Diff::compare(&old.variant, &old.computed, &new.computed);
+
+    println!("{diff}");
+    println!("{:<16} {:<16} {:<16} {:>9} {:>9}", "Hand", "Old hold", "New hold", "Old EV", "New EV");
+
+    for change in diff.changes.iter().take(WORST_HANDS) {
+        println!(
+            "{:<16} {:<16} {:<16} {:>9.4} {:>9.4}",
+            held(&change.hand, &[0, 1, 2, 3, 4]), held(&change.hand, &change.old_keep), held(&change.hand, &change.new_keep), change.old_score, change.new_score
+        );
+    }
+
+    Ok(())
+}
+
+fn audit(file: &str, game_name: &str, state_file: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let (variant, paytable) = game::resolve(game_name)?;
+
+    let history = History::parse(&fs::read_to_string(file)?, &variant.deck())?;
+    log::info!("Loaded {} hands from `{file}`.", history.records.len());
+
+    let table = draw_table(variant, &paytable);
+
+    let audit = match state_file {
+        Some(state_file) => {
+            let strategy = Strategy::new(state::load_variant_moves(state_file, variant)?);
+
+            Audit::new(&variant, &table, &history, &Computed { strategy: &strategy, table: &table })
+        },
+        None => Audit::new(&variant, &table, &history, &Optimal(&table)),
+    };
+
+    println!("{audit}");
+
+    if !audit.mismatches.is_empty() {
+        println!("{:>6} {:<16} {:>8} {:>8}", "Line", "Final hand", "Observed", "Paytable");
+
+        for mismatch in &audit.mismatches {
+            println!("{:>6} {:<16} {:>8} {:>8}", mismatch.line, held(&mismatch.drawn, &[0, 1, 2, 3, 4]), mismatch.observed, mismatch.expected);
+        }
+
+        println!();
+    }
+
+    let mut mistakes: Vec<_> = audit.mistakes.iter().collect();
+    mistakes.sort_by(|a, b| b.loss.total_cmp(&a.loss));
+
+    println!("{:>6} {:<16} {:<16} {:<16} {:>9}", "Line", "Hand", "Held", "Optimal", "EV lost");
+
+    for mistake in mistakes.into_iter().take(WORST_HANDS) {
+        println!(
+            "{:>6} {:<16} {:<16} {:<16} {:>9.4}",
+            mistake.line, held(&mistake.dealt, &[0, 1, 2, 3, 4]), held(&mistake.dealt, &mistake.keep), held(&mistake.dealt, &mistake.optimal), mistake.loss
+        );
+    }
+
+    Ok(())
+}
+
+fn dead(game_name: &str, hand: &str, dead: &str) -> Result<(), Box<dyn Error>> {
+    let (variant, paytable) = game::resolve(game_name)?;
+
+    let deck = variant.deck();
+    let hand = parse_deck_hand(hand, &deck)?;
+    let dead = parse_deck_cards(dead, &deck)?;
+
+    let r#move = video_poker::calculate_dead_card_move(&variant, &paytable, &hand, &dead);
+
+    println!("Hand:               {}", held(&hand, &[0, 1, 2, 3, 4]));
+    println!("Dead cards:         {}", dead.iter().map(Card::to_string).collect::<Vec<_>>().join(" "));
+    println!("Standard hold:      {} (EV {:.6})", held(&hand, &r#move.standard.keep), r#move.standard.average_score);
+    println!("Adjusted hold:      {} (EV {:.6})", held(&hand, &r#move.adjusted.keep), r#move.adjusted.average_score);
+    println!("Difference:         {:+.6}{}", r#move.difference(), if r#move.hold_changed() { " (hold changed)" } else { "" });
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(error) = simple_logger::SimpleLogger::new().env().init() {
+        eprintln!("Logger initialization failed: {}", error);
+        
+        std::process::exit(1);
+    }
+
+    let args: Vec<String> = env::args().collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        [_, "export", file, output] => export(file, output),
+        [_, "report", game] => report(game, None),
+        [_, "report", game, file] => report(game, Some(file)),
+        [_, "risk", bankroll, games, game] => risk(bankroll, games, game, None),
+        [_, "risk", bankroll, games, game, file] => risk(bankroll, games, game, Some(file)),
+        [_, "simulate", hands, seed, game] => simulate(hands, seed, game, None),
+        [_, "simulate", hands, seed, game, strategy] => simulate(hands, seed, game, Some(strategy)),
+        [_, "chart", file, game] => chart(file, game, None),
+        [_, "chart", file, game, state_file] => chart(file, game, Some(state_file)),
+        [_, "synthesize", lines, output, game] => synthesize(lines, output, game, None),
+        [_, "synthesize", lines, output, game, state_file] => synthesize(lines, output, game, Some(state_file)),
+        [_, "dead", game, hand, dead_cards] => dead(game, hand, dead_cards),
+        [_, "multiplay", deals, game] => multiplay(deals, game, None),
+        [_, "multiplay", deals, game, state_file] => multiplay(deals, game, Some(state_file)),
+        [_, "progressive", max_royal, game] => progressive(max_royal, game),
+        [_, "diff", old_file, new_file] => diff(old_file, new_file),
+        [_, "audit", file, game] => audit(file, game, None),
+        [_, "audit", file, game, state_file] => audit(file, game, Some(state_file)),
+        [binary, ..] => {
+            usage(binary);
+
+            std::process::exit(1);
+        },
+        [] => unreachable!(),
+    };
+
+    if let Err(error) = result {
+        log::error!("Fatal: {}", error);
+
+        std::process::exit(1);
+    }
+}
+
+fn usage(binary: &str) {
+    log::error!("Usage: {binary} export <state file> <output file (.md, .html or .csv)>");
+    log::error!("       {binary} report <game> [state file]");
+    log::error!("       {binary} risk <bankroll (coins)> <games> <game> [state file]");
+    log::error!("       {binary} simulate <hands>[x<lines>] <seed> <game> [state file | chart:<state file>]");
+    log::error!("       {binary} chart <priority list> <game> [state file]");
+    log::error!("       {binary} synthesize <max lines> <output file> <game> [state file]");
+    log::error!("       {binary} dead <game> <hand> <dead cards>");
+    log::error!("       {binary} multiplay <session deals> <game> [state file]");
+    log::error!("       {binary} progressive <max royal flush payout (per coin)> <game>");
+    log::error!("       {binary} diff <old state file | chart (.md or .csv)> <new state file | chart>");
+    log::error!("       {binary} audit <hand history> <game> [state file]");
+    log::error!("A game is a variant, a paytable preset or `<variant>:<paytable (preset or JSON file)>`.");
+}
@@ -0,0 +1,152 @@
+//! Multi-play video poker (e.g. Triple Play, Ten Play): the held cards are copied to several lines, each
+//! drawing from its own deck. Every line has the same return as a single line, but the lines of a deal
+//! are correlated through the shared dealt hand, which changes the variance a lot.
+
+use core::fmt;
+
+use crate::{draw::DrawTable, policy::Policy, report, risk::diffusion_risk_of_ruin};
+
+/// The amounts of lines of the common multi-play games.
+pub const LINES: [usize; 6] = [1, 3, 5, 10, 50, 100];
+
+/// The risks of ruin the bankroll requirements are computed for.
+pub const RISKS: [f64; 2] = [0.05, 0.01];
+
+/// The moments of the payout of a line under a policy, computed exactly from the per-hold outcome distributions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Moments {
+    /// The expected payout per coin bet.
+    pub mean: f64,
+    /// The expected squared payout.
+    pub second_moment: f64,
+    /// The expected square of the expected payout of the hold, taken over the dealt hands.
+    pub hold_second_moment: f64,
+}
+
+impl Moments {
+    /// Plays every possible deal by the policy.
+    pub fn from_policy(table: &DrawTable, policy: &impl Policy) -> Self {
+        let mut moments = Moments { mean: 0f64, second_moment: 0f64, hold_second_moment: 0f64 };
+
+        for hand in report::classes(table) {
+            let weight = report::class_weight(table, &hand);
+            let outcome = table.outcome(&hand, &policy.hold(&hand));
+            let mean = outcome.expected_value(table.paytable());
+
+            moments.mean += weight * mean;
+            moments.second_moment += weight * outcome.second_moment(table.paytable());
+            moments.hold_second_moment += weight * mean * mean;
+        }
+
+        moments
+    }
+
+    /// The variance of the payout of a single line.
+    pub fn variance(&self) -> f64 {
+        self.second_moment - self.mean * self.mean
+    }
+
+    /// The covariance of the payouts of two lines of the same deal. The lines only share the dealt hand,
+    /// so this is the variance of the expected payout of the hold.
+    pub fn covariance(&self) -> f64 {
+        self.hold_second_moment - self.mean * self.mean
+    }
+
+    /// The variance of the summed payout of a deal played on the given amount of lines.
+    pub fn deal_variance(&self, lines: usize) -> f64 {
+        let lines = lines as f64;
+
+        lines * self.variance() + lines * (lines - 1f64) * self.covariance()
+    }
+}
+
+/// The smallest bankroll (in coins) keeping the risk of ruin within the given amount of deals below `risk`.
+pub fn bankroll_requirement(drift: f64, variance: f64, deals: f64, risk: f64) -> f64 {
+    let mut high = variance.sqrt().max(1f64);
+
+    while diffusion_risk_of_ruin(drift, variance, high, deals) > risk {
+        high *= 2f64;
+    }
+
+    let mut low = 0f64;
+
+    for _ in 0..100 {
+        let middle = (low + high) / 2f64;
+
+        match diffusion_risk_of_ruin(drift, variance, middle, deals) > risk {
+            true => low = middle,
+            false => high = middle,
+        }
+    }
+
+    high
+}
+
+/// The figures of a game played on a given amount of lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiPlayRow {
+    pub lines: usize,
+    /// The standard deviation of the payout of a deal, in coins.
+    pub deal_deviation: f64,
+    /// The standard deviation of the payout of a deal per coin bet.
+    pub coin_deviation: f64,
+    /// The bankroll (in coins) for each of the [RISKS] of ruin within the session.
+    pub bankrolls: Vec<f64>,
+}
+
+/// The variance and bankroll requirements of a game for the common amounts of lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiPlayReport {
+    pub moments: Moments,
+    /// The amount of deals of a session.
+    pub deals: usize,
+    pub rows: Vec<MultiPlayRow>,
+}
+
+impl MultiPlayReport {
+    pub fn new(moments: Moments, deals: usize) -> Self {
+        let rows = LINES
+            .iter()
+            .map(|&lines| {
+                let variance = moments.deal_variance(lines);
+                let drift = lines as f64 * (moments.mean - 1f64);
+
+                MultiPlayRow {
+                    lines,
+                    deal_deviation: variance.sqrt(),
+                    coin_deviation: variance.sqrt() / lines as f64,
+                    bankrolls: RISKS.iter().map(|&risk| bankroll_requirement(drift, variance, deals as f64, risk)).collect(),
+                }
+            })
+            .collect();
+
+        Self { moments, deals, rows }
+    }
+}
+
+impl fmt::Display for MultiPlayReport {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "Return:             {:.4}%", self.moments.mean * 100f64)?;
+        writeln!(formatter, "Variance:           {:.4}", self.moments.variance())?;
+        writeln!(formatter, "Covariance:         {:.4}", self.moments.covariance())?;
+        writeln!(formatter, "Correlation:        {:.4}", self.moments.covariance() / self.moments.variance())?;
+        writeln!(formatter)?;
+
+        write!(formatter, "{:>6} {:>12} {:>12}", "Lines", "SD per deal", "SD per coin")?;
+        for risk in RISKS {
+            write!(formatter, " {:>16}", format!("Bankroll ({}%)", risk * 100f64))?;
+        }
+        writeln!(formatter)?;
+
+        for row in &self.rows {
+            write!(formatter, "{:>6} {:>12.4} {:>12.4}", row.lines, row.deal_deviation, row.coin_deviation)?;
+            for bankroll in &row.bankrolls {
+                write!(formatter, " {bankroll:>16.0}")?;
+            }
+            writeln!(formatter)?;
+        }
+
+        writeln!(formatter)?;
+        write!(formatter, "Bankrolls in coins for a risk of ruin within {} deals.", self.deals)
+    }
+}
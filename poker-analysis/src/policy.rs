@@ -0,0 +1,81 @@
+//! Strategies deciding which cards of a dealt hand to hold.
+
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use poker_base::Card;
+
+use crate::{draw::DrawTable, export::Chart, hold::{self, HoldClass}, strategy::Strategy};
+
+/// Decides which cards of a dealt hand to hold.
+pub trait Policy: Sync {
+    /// The indices of the held cards, ascending.
+    fn hold(&self, hand: &[Card; 5]) -> Vec<usize>;
+}
+
+/// Any function of the hand is a policy, e.g. a user-supplied strategy.
+impl<F: Fn(&[Card; 5]) -> Vec<usize> + Sync> Policy for F {
+    fn hold(&self, hand: &[Card; 5]) -> Vec<usize> {
+        self(hand)
+    }
+}
+
+/// Optimal play, solving every hand with the draw table.
+pub struct Optimal<'a>(pub &'a DrawTable);
+
+impl Policy for Optimal<'_> {
+    fn hold(&self, hand: &[Card; 5]) -> Vec<usize> {
+        self.0.optimal(hand).keep
+    }
+}
+
+/// The holds of a computed strategy, solving the hands it does not cover with the draw table.
+pub struct Computed<'a> {
+    pub strategy: &'a Strategy,
+    pub table: &'a DrawTable,
+}
+
+impl Policy for Computed<'_> {
+    fn hold(&self, hand: &[Card; 5]) -> Vec<usize> {
+        match self.strategy.lookup(hand) {
+            Some(r#move) => r#move.keep,
+            None => self.table.optimal(hand).keep,
+        }
+    }
+}
+
+/// Playing by a hold-priority chart: of all holds of the hand, the one whose class comes first in the
+/// chart is held. Hands without any hold in the chart are discarded entirely.
+pub struct Priorities {
+    priorities: HashMap<HoldClass, usize>,
+    /// The wild cards of the game.
+    wilds: Vec<Card>,
+}
+
+impl Priorities {
+    pub fn new(classes: impl IntoIterator<Item = HoldClass>, wilds: &[Card]) -> Self {
+        let mut priorities = HashMap::new();
+
+        for (priority, class) in classes.into_iter().enumerate() {
+            priorities.entry(class).or_insert(priority);
+        }
+
+        Self { priorities, wilds: wilds.to_vec() }
+    }
+
+    /// Plays by the chart of a game of the given wild cards.
+    pub fn from_chart(chart: &Chart, wilds: &[Card]) -> Self {
+        Self::new(chart.entries.iter().map(|entry| entry.class.clone()), wilds)
+    }
+}
+
+impl Policy for Priorities {
+    fn hold(&self, hand: &[Card; 5]) -> Vec<usize> {
+        (0..hand.len())
+            .powerset()
+            .filter_map(|keep| self.priorities.get(&hold::classify(hand, &keep, |card| self.wilds.contains(&card))).map(|&priority| (priority, keep)))
+            .min_by_key(|(priority, _)| *priority)
+            .map(|(_, keep)| keep)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,343 @@
+//! Simplified strategies: hold-priority lists written in a small language, one rule per line, e.g.
+//!
+//! ```text
+//! # Jacks or Better, simple strategy
+//! Four of a kind
+//! 4 to a royal flush
+//! High pair
+//! 3 to a straight flush, 1 gap, no flush penalty
+//! 4 to an outside straight
+//! KQJ unsuited
+//! 2 high cards suited
+//! Discard everything
+//! ```
+//!
+//! A rule names a made hand, a draw (`<n> to a royal flush`, `... straight flush`, `... flush`,
+//! `... [outside | inside] straight`), high cards (`<n> high cards [suited | unsuited]`), exact values
+//! (`KQJT suited`, `A`) or `Discard everything`. It may be narrowed down by comma separated qualifiers:
+//! `<n> gaps` (or `open`), `<n> high cards`, `[no] flush penalty`, `[no] straight penalty` and `no penalty`.
+//! A draw that can only be completed at one end (e.g. A234 or JQKA) counts one gap more than it is missing inside.
+//! A hand is played by the first rule that any of its holds matches.
+
+use core::{fmt, str::FromStr};
+use std::error::Error;
+
+use itertools::Itertools;
+use poker_base::{Card, Value};
+
+use crate::{hold::{self, HoldClass, HoldKind, MadeHand}, policy::Policy};
+
+/// The kind of hold a rule describes.
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub enum Shape {
+    Made(MadeHand),
+    /// Suited cards of ten or higher.
+    RoyalFlushDraw,
+    /// Suited cards that fit into a straight.
+    StraightFlushDraw,
+    /// Suited cards.
+    FlushDraw,
+    /// Distinct values that fit into a straight. An outside draw has no gaps and can be completed on both ends.
+    StraightDraw { outside: Option<bool> },
+    /// Distinct cards of jack or higher.
+    HighCards { suited: Option<bool> },
+    /// Exactly the given values.
+    Values { values: Vec<Value>, suited: Option<bool> },
+    DiscardAll,
+}
+
+/// A line of a hold-priority list.
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub struct Rule {
+    pub shape: Shape,
+    /// The amount of held cards.
+    pub cards: Option<usize>,
+    /// The amount of values missing inside a straight (flush) draw, a draw that can only be completed at one end counting one more.
+    pub gaps: Option<usize>,
+    /// The amount of held cards of jack or higher.
+    pub high_cards: Option<usize>,
+    /// Whether a card of the held suit is discarded.
+    pub flush_penalty: Option<bool>,
+    /// Whether a card that could complete a straight with the held cards is discarded.
+    pub straight_penalty: Option<bool>,
+}
+
+impl Rule {
+    pub fn new(shape: Shape) -> Self {
+        Self { shape, cards: None, gaps: None, high_cards: None, flush_penalty: None, straight_penalty: None }
+    }
+
+    /// Whether the hold of the given cards (classified as `class`) matches the rule.
+    pub fn matches(&self, class: &HoldClass, cards: &[Card]) -> bool {
+        let values: Vec<Value> = cards.iter().map(|card| card.value).collect();
+
+        let natural = !cards.is_empty() && cards.iter().all(|card| !card.is_joker());
+        let distinct = values.iter().all_unique();
+        let suited = natural && hold::is_suited(cards);
+        let straight = match natural && distinct {
+            true => hold::straight_ranks(&values),
+            false => None,
+        };
+
+        let shape = match &self.shape {
+            Shape::Made(made) => class.kind == HoldKind::Made(*made),
+            Shape::RoyalFlushDraw => suited && values.iter().all(|&value| value >= Value::Ten),
+            Shape::StraightFlushDraw => suited && straight.is_some(),
+            Shape::FlushDraw => suited,
+            Shape::StraightDraw { outside } => straight.as_ref().is_some_and(|ranks| outside.is_none_or(|outside| outside == hold::is_outside(ranks))),
+            Shape::HighCards { suited: wanted } => {
+                natural && distinct && values.iter().all(|&value| hold::is_high(value)) && wanted.is_none_or(|wanted| wanted == suited)
+            },
+            Shape::Values { values: wanted, suited: wanted_suited } => {
+                values.iter().sorted().eq(wanted.iter().sorted()) && wanted_suited.is_none_or(|wanted| wanted == suited)
+            },
+            Shape::DiscardAll => cards.is_empty(),
+        };
+
+        let gaps = straight.as_ref().map(|ranks| hold::gaps(ranks));
+        let high_cards = values.iter().filter(|&&value| value != Value::Joker && hold::is_high(value)).count();
+
+        shape
+            && self.cards.is_none_or(|wanted| wanted == cards.len())
+            && self.gaps.is_none_or(|wanted| gaps == Some(wanted))
+            && self.high_cards.is_none_or(|wanted| wanted == high_cards)
+            && self.flush_penalty.is_none_or(|wanted| wanted == class.penalties.flush)
+            && self.straight_penalty.is_none_or(|wanted| wanted == class.penalties.straight)
+    }
+}
+
+fn suitedness(suited: &Option<bool>) -> &'static str {
+    match suited {
+        Some(true) => " suited",
+        Some(false) => " unsuited",
+        None => "",
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cards = self.cards.map(|cards| cards.to_string()).unwrap_or_else(|| "any".to_string());
+
+        match &self.shape {
+            Shape::Made(made) => write!(formatter, "{made}")?,
+            Shape::RoyalFlushDraw => write!(formatter, "{cards} to a royal flush")?,
+            Shape::StraightFlushDraw => write!(formatter, "{cards} to a straight flush")?,
+            Shape::FlushDraw => write!(formatter, "{cards} to a flush")?,
+            Shape::StraightDraw { outside: Some(true) } => write!(formatter, "{cards} to an outside straight")?,
+            Shape::StraightDraw { outside: Some(false) } => write!(formatter, "{cards} to an inside straight")?,
+            Shape::StraightDraw { outside: None } => write!(formatter, "{cards} to a straight")?,
+            Shape::HighCards { suited } => write!(formatter, "{cards} high {}{}", if self.cards == Some(1) { "card" } else { "cards" }, suitedness(suited))?,
+            Shape::Values { values, suited } => write!(formatter, "{}{}", values.iter().join(""), suitedness(suited))?,
+            Shape::DiscardAll => write!(formatter, "Discard everything")?,
+        }
+
+        if let Some(gaps) = self.gaps {
+            write!(formatter, ", {gaps} {}", if gaps == 1 { "gap" } else { "gaps" })?;
+        }
+
+        if let Some(high_cards) = self.high_cards {
+            write!(formatter, ", {high_cards} high {}", if high_cards == 1 { "card" } else { "cards" })?;
+        }
+
+        for (penalty, name) in [(self.flush_penalty, "flush"), (self.straight_penalty, "straight")] {
+            match penalty {
+                Some(true) => write!(formatter, ", {name} penalty")?,
+                Some(false) => write!(formatter, ", no {name} penalty")?,
+                None => {},
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// What is wrong with a rule.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum RuleErrorKind {
+    /// The words do not describe a hold, e.g. `5 to a banana`.
+    UnknownHold(String),
+    /// The amount of cards, gaps or high cards is not a number.
+    InvalidAmount(String),
+    /// The qualifier is none of the known ones.
+    UnknownQualifier(String),
+}
+
+impl fmt::Display for RuleErrorKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleErrorKind::UnknownHold(hold) => write!(formatter, "unknown hold `{hold}`"),
+            RuleErrorKind::InvalidAmount(amount) => write!(formatter, "invalid amount `{amount}`"),
+            RuleErrorKind::UnknownQualifier(qualifier) => write!(formatter, "unknown qualifier `{qualifier}`"),
+        }
+    }
+}
+
+impl Error for RuleErrorKind {}
+
+/// An error parsing a hold-priority list.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct RuleError {
+    /// The line of the rule, starting at one.
+    pub line: usize,
+    pub rule: String,
+    pub kind: RuleErrorKind,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "invalid rule `{}` on line {}: {}", self.rule, self.line, self.kind)
+    }
+}
+
+impl Error for RuleError {}
+
+fn parse_amount(amount: &str) -> Result<usize, RuleErrorKind> {
+    amount.parse().map_err(|_| RuleErrorKind::InvalidAmount(amount.to_string()))
+}
+
+fn parse_suited(words: &[&str]) -> Option<Option<bool>> {
+    match words {
+        [] => Some(None),
+        ["suited"] => Some(Some(true)),
+        ["unsuited" | "offsuit"] => Some(Some(false)),
+        _ => None,
+    }
+}
+
+fn parse_shape(base: &str) -> Result<Rule, RuleErrorKind> {
+    if let Some(made) = MadeHand::ALL.into_iter().find(|made| made.to_string().to_lowercase() == base) {
+        return Ok(Rule::new(Shape::Made(made)));
+    }
+
+    let unknown = || RuleErrorKind::UnknownHold(base.to_string());
+
+    let words: Vec<&str> = base.split_whitespace().collect();
+
+    let (shape, cards) = match words.as_slice() {
+        ["discard", "everything" | "all"] | ["nothing"] => (Shape::DiscardAll, Some(0)),
+        [cards, "to", draw @ ..] => {
+            let draw = match draw {
+                ["a" | "an", draw @ ..] => draw,
+                draw => draw,
+            };
+
+            let shape = match draw {
+                ["royal"] | ["royal", "flush"] => Shape::RoyalFlushDraw,
+                ["straight", "flush"] => Shape::StraightFlushDraw,
+                ["flush"] => Shape::FlushDraw,
+                ["straight"] => Shape::StraightDraw { outside: None },
+                ["outside", "straight"] => Shape::StraightDraw { outside: Some(true) },
+                ["inside", "straight"] => Shape::StraightDraw { outside: Some(false) },
+                _ => return Err(unknown()),
+            };
+
+            (shape, Some(parse_amount(cards)?))
+        },
+        [cards, "high", "card" | "cards", suited @ ..] => {
+            (Shape::HighCards { suited: parse_suited(suited).ok_or_else(unknown)? }, Some(parse_amount(cards)?))
+        },
+        [values, suited @ ..] => {
+            let values: Vec<Value> = values.chars().map(Value::try_from).collect::<Result<_, _>>().map_err(|_| unknown())?;
+
+            (Shape::Values { values, suited: parse_suited(suited).ok_or_else(unknown)? }, None)
+        },
+        [] => return Err(unknown()),
+    };
+
+    Ok(Rule { cards, ..Rule::new(shape) })
+}
+
+impl FromStr for Rule {
+    type Err = RuleErrorKind;
+
+    /// Parses a rule, ignoring case.
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let rule = rule.to_lowercase();
+        let mut parts = rule.split(',').map(str::trim);
+
+        let mut parsed = parse_shape(parts.next().unwrap_or_default())?;
+
+        for qualifier in parts {
+            let words: Vec<&str> = qualifier.split_whitespace().collect();
+
+            match words.as_slice() {
+                ["open"] => parsed.gaps = Some(0),
+                [gaps, "gap" | "gaps"] => parsed.gaps = Some(parse_amount(gaps)?),
+                [high_cards, "high", "card" | "cards"] => parsed.high_cards = Some(parse_amount(high_cards)?),
+                ["flush", "penalty"] => parsed.flush_penalty = Some(true),
+                ["no", "flush", "penalty"] => parsed.flush_penalty = Some(false),
+                ["straight", "penalty"] => parsed.straight_penalty = Some(true),
+                ["no", "straight", "penalty"] => parsed.straight_penalty = Some(false),
+                ["no", "penalty" | "penalties"] => {
+                    parsed.flush_penalty = Some(false);
+                    parsed.straight_penalty = Some(false);
+                },
+                _ => return Err(RuleErrorKind::UnknownQualifier(qualifier.to_string())),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// A simplified strategy: rules ordered from the most to the least valuable hold.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct PriorityList {
+    pub rules: Vec<Rule>,
+}
+
+impl PriorityList {
+    /// The first rule any hold of the hand matches and the hold. Of several holds matching the rule,
+    /// the one keeping the fewest cards (then the first by position) is chosen.
+    pub fn choose(&self, hand: &[Card; 5], is_wild: impl Fn(Card) -> bool + Copy) -> Option<(usize, Vec<usize>)> {
+        let holds: Vec<(Vec<usize>, HoldClass, Vec<Card>)> = (0..hand.len())
+            .powerset()
+            .map(|keep| {
+                let class = hold::classify(hand, &keep, is_wild);
+                let cards = keep.iter().map(|&index| hand[index]).collect();
+
+                (keep, class, cards)
+            })
+            .collect();
+
+        self.rules.iter().enumerate().find_map(|(index, rule)| {
+            holds.iter().find(|(_, class, cards)| rule.matches(class, cards)).map(|(keep, _, _)| (index, keep.clone()))
+        })
+    }
+
+    /// Plays by the list in a game of the given wild cards. Hands matching no rule are discarded entirely.
+    pub fn policy<'a>(&'a self, is_wild: impl Fn(Card) -> bool + Copy + Sync + 'a) -> impl Policy + 'a {
+        move |hand: &[Card; 5]| self.choose(hand, is_wild).map(|(_, keep)| keep).unwrap_or_default()
+    }
+}
+
+impl FromStr for PriorityList {
+    type Err = RuleError;
+
+    /// Parses one rule per line, skipping empty lines and `#` comments.
+    fn from_str(list: &str) -> Result<Self, Self::Err> {
+        let mut rules = Vec::new();
+
+        for (index, line) in list.lines().enumerate() {
+            let rule = line.split('#').next().unwrap().trim();
+
+            if rule.is_empty() {
+                continue;
+            }
+
+            rules.push(rule.parse().map_err(|kind| RuleError { line: index + 1, rule: rule.to_string(), kind })?);
+        }
+
+        Ok(Self { rules })
+    }
+}
+
+impl fmt::Display for PriorityList {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rule in &self.rules {
+            writeln!(formatter, "{rule}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+//! Progressive jackpots: the return and the optimal strategy as functions of the royal flush payout.
+//!
+//! The expected value of each hold is linear in the royal flush payout, so the optimal hold of a deal
+//! only changes where the lines of two holds cross. Following the upper envelope of the 32 lines of
+//! every deal gives the return exactly as a piecewise linear function, and every hold change with it.
+
+use core::fmt;
+use std::collections::{BTreeMap, HashSet};
+
+use poker_base::{Card, Category, Paytable};
+
+use crate::{draw::DrawTable, hold::{self, HoldClass}, report};
+
+/// The amount of coins of a max bet, which progressive meters show the jackpot for.
+pub const MAX_COINS: usize = 5;
+
+/// The amount of royal flush payouts the return is tabulated at.
+const STEPS: usize = 10;
+
+/// The paytable with the royal flush paying the given amount per coin, if it has a royal flush category.
+pub fn with_royal(paytable: &Paytable, royal: usize) -> Option<Paytable> {
+    let mut paytable = paytable.clone();
+
+    paytable.payouts.iter_mut().find(|payout| payout.category == Category::RoyalFlush)?.payout = royal;
+
+    Some(paytable)
+}
+
+/// A part of the return function: from the given royal flush payout on (up to the next segment),
+/// the return is `intercept + slope * royal`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub from: f64,
+    pub intercept: f64,
+    pub slope: f64,
+}
+
+/// The optimal hold of a deal changing as the royal flush payout grows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoldChange {
+    /// The canonical representative of the hand's suit-isomorphism class.
+    pub hand: [Card; 5],
+    /// The royal flush payout from which on the new hold is optimal.
+    pub royal: f64,
+    pub from: Vec<usize>,
+    pub to: Vec<usize>,
+    /// The probability of being dealt any hand of the class.
+    pub probability: f64,
+}
+
+/// Hold changes grouped by the pattern classes of the holds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    pub from: HoldClass,
+    pub to: HoldClass,
+    /// The lowest royal flush payout at which any of the changes happens.
+    pub royal: f64,
+    /// The probability of being dealt any of the changing hands.
+    pub probability: f64,
+}
+
+/// The return and strategy of a game over a range of royal flush payouts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progressive {
+    pub paytable: String,
+    /// The royal flush payout of the paytable, where the analysis starts.
+    pub royal: usize,
+    pub max_royal: f64,
+    /// The return of keeping the strategy of the paytable's royal flush payout.
+    pub fixed: Segment,
+    /// Ordered by the royal flush payout they start at.
+    pub segments: Vec<Segment>,
+    /// Ordered by the royal flush payout they happen at.
+    pub changes: Vec<HoldChange>,
+    /// The wild cards of the game, classifying the changed holds.
+    pub wilds: Vec<Card>,
+}
+
+/// The expected value of a hold as `intercept + slope * royal`.
+#[derive(Debug, Clone, Copy)]
+struct Line {
+    intercept: f64,
+    slope: f64,
+}
+
+impl Line {
+    /// The royal flush payout at which the other, steeper line overtakes this one.
+    fn crossing(&self, other: &Line) -> f64 {
+        (self.intercept - other.intercept) / (other.slope - self.slope)
+    }
+}
+
+impl Progressive {
+    /// Follows the optimal strategy of every possible deal from the paytable's royal flush payout up to `max_royal`.
+    /// Returns `None` if the paytable has no royal flush.
+    pub fn analyze(table: &DrawTable, max_royal: f64) -> Option<Self> {
+        let paytable = table.paytable();
+        let category = paytable.payouts.iter().position(|payout| payout.category == Category::RoyalFlush)?;
+        let royal = paytable.payouts[category].payout;
+        let without_royal = with_royal(paytable, 0)?;
+
+        let (mut intercept, mut slope) = (0f64, 0f64);
+        // the changes of the intercept and the slope of the return at each crossing
+        let mut events: Vec<(f64, f64, f64)> = Vec::new();
+        let mut changes: Vec<HoldChange> = Vec::new();
+
+        for hand in report::classes(table) {
+            let probability = report::class_weight(table, &hand);
+            let outcomes = table.outcomes(&hand);
+
+            let lines: Vec<Line> = outcomes
+                .iter()
+                .map(|outcome| Line {
+                    intercept: outcome.expected_value(&without_royal),
+                    slope: outcome.counts[category] as f64 / outcome.total() as f64,
+                })
+                .collect();
+
+            // the optimal hold at the paytable's payout, preferring the steeper of equally good lines
+            let value = |line: &Line| line.intercept + line.slope * royal as f64;
+            let mut current = (0..lines.len())
+                .max_by(|&a, &b| value(&lines[a]).total_cmp(&value(&lines[b])).then(lines[a].slope.total_cmp(&lines[b].slope)))
+                .unwrap();
+
+            intercept += probability * lines[current].intercept;
+            slope += probability * lines[current].slope;
+
+            loop {
+                let next = (0..lines.len())
+                    .filter(|&index| lines[index].slope > lines[current].slope)
+                    .map(|index| (index, lines[current].crossing(&lines[index])))
+                    .min_by(|(a, crossing_a), (b, crossing_b)| crossing_a.total_cmp(crossing_b).then(lines[*b].slope.total_cmp(&lines[*a].slope)));
+
+                let Some((next, crossing)) = next.filter(|&(_, crossing)| crossing <= max_royal) else { break };
+                let crossing = crossing.max(royal as f64);
+
+                events.push((
+                    crossing,
+                    probability * (lines[next].intercept - lines[current].intercept),
+                    probability * (lines[next].slope - lines[current].slope),
+                ));
+
+                changes.push(HoldChange {
+                    hand,
+                    royal: crossing,
+                    from: outcomes[current].keep.clone(),
+                    to: outcomes[next].keep.clone(),
+                    probability,
+                });
+
+                current = next;
+            }
+        }
+
+        events.sort_by(|a, b| a.0.total_cmp(&b.0));
+        changes.sort_by(|a, b| a.royal.total_cmp(&b.royal));
+
+        let fixed = Segment { from: royal as f64, intercept, slope };
+        let mut segments = vec![fixed];
+
+        for (from, intercept, slope) in events {
+            let last = *segments.last().unwrap();
+            let segment = Segment { from, intercept: last.intercept + intercept, slope: last.slope + slope };
+
+            match last.from == from {
+                true => *segments.last_mut().unwrap() = segment,
+                false => segments.push(segment),
+            }
+        }
+
+        Some(Self { paytable: paytable.name.clone(), royal, max_royal, fixed, segments, changes, wilds: table.wilds().to_vec() })
+    }
+
+    /// The return of optimal play for the given royal flush payout (within the analysed range).
+    pub fn expected_return(&self, royal: f64) -> f64 {
+        let segment = self.segments.iter().rev().find(|segment| segment.from <= royal).unwrap_or(&self.segments[0]);
+
+        segment.intercept + segment.slope * royal
+    }
+
+    /// The return of keeping the strategy of the paytable's royal flush payout at the given one.
+    pub fn fixed_strategy_return(&self, royal: f64) -> f64 {
+        self.fixed.intercept + self.fixed.slope * royal
+    }
+
+    /// The lowest royal flush payout (from the paytable's on) at which optimal play returns at least
+    /// the bet, if it is reached within the analysed range.
+    pub fn breakeven(&self) -> Option<f64> {
+        self.segments.iter().enumerate().find_map(|(index, segment)| {
+            let to = self.segments.get(index + 1).map_or(self.max_royal, |next| next.from);
+            let royal = match segment.slope > 0f64 {
+                true => ((1f64 - segment.intercept) / segment.slope).max(segment.from),
+                false => segment.from,
+            };
+
+            (royal <= to && segment.intercept + segment.slope * royal >= 1f64 - 1e-12).then_some(royal)
+        })
+    }
+
+    /// The probability of being dealt a hand whose optimal hold at the given royal flush payout differs
+    /// from the one at the paytable's.
+    pub fn changed_frequency(&self, royal: f64) -> f64 {
+        let mut seen = HashSet::new();
+
+        self.changes
+            .iter()
+            .take_while(|change| change.royal <= royal)
+            .filter(|change| seen.insert(change.hand))
+            .fold(0f64, |total, change| total + change.probability)
+    }
+
+    /// The hold changes grouped by the pattern classes of the holds, ordered by the payout they start at.
+    pub fn transitions(&self) -> Vec<Transition> {
+        let mut transitions: BTreeMap<(HoldClass, HoldClass), Transition> = BTreeMap::new();
+
+        for change in &self.changes {
+            let (from, to) = (hold::classify(&change.hand, &change.from, |card| self.wilds.contains(&card)), hold::classify(&change.hand, &change.to, |card| self.wilds.contains(&card)));
+
+            let transition = transitions
+                .entry((from.clone(), to.clone()))
+                .or_insert(Transition { from, to, royal: change.royal, probability: 0f64 });
+
+            transition.royal = transition.royal.min(change.royal);
+            transition.probability += change.probability;
+        }
+
+        let mut transitions: Vec<Transition> = transitions.into_values().collect();
+        transitions.sort_by(|a, b| a.royal.total_cmp(&b.royal));
+
+        transitions
+    }
+}
+
+impl fmt::Display for Progressive {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let royal = self.royal as f64;
+
+        writeln!(formatter, "Paytable:           {}", self.paytable)?;
+        writeln!(formatter, "Royal flush:        {}", self.royal)?;
+        writeln!(formatter, "Return:             {:.4}%", self.expected_return(royal) * 100f64)?;
+
+        match self.breakeven() {
+            Some(breakeven) if breakeven <= royal => writeln!(formatter, "Breakeven royal:    reached at the paytable's payout")?,
+            Some(breakeven) => writeln!(
+                formatter, "Breakeven royal:    {breakeven:.1} per coin (meter of {:.0} for {MAX_COINS} coins)", breakeven * MAX_COINS as f64
+            )?,
+            None => writeln!(formatter, "Breakeven royal:    above {:.0} per coin", self.max_royal)?,
+        }
+
+        writeln!(formatter)?;
+        writeln!(formatter, "{:>10} {:>10} {:>14} {:>14} {:>14}", "Royal", "Meter", "Return", "Fixed return", "Changed deals")?;
+
+        for step in 0..=STEPS {
+            let royal = royal + (self.max_royal - royal) * step as f64 / STEPS as f64;
+
+            writeln!(
+                formatter, "{royal:>10.0} {:>10.0} {:>13.4}% {:>13.4}% {:>13.4}%",
+                royal * MAX_COINS as f64,
+                self.expected_return(royal) * 100f64,
+                self.fixed_strategy_return(royal) * 100f64,
+                self.changed_frequency(royal) * 100f64,
+            )?;
+        }
+
+        writeln!(formatter)?;
+        writeln!(formatter, "{:>10} {:>12}  Hold change", "From royal", "Probability")?;
+
+        for transition in self.transitions() {
+            writeln!(formatter, "{:>10.1} {:>11.6}%  {} -> {}", transition.royal, transition.probability * 100f64, transition.from, transition.to)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+//! The overall return of a game under a given strategy.
+
+use core::fmt;
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use poker_base::{canonical, Card, ComputedMove};
+
+use crate::{draw::{self, DrawTable, Outcome}, policy::Policy, state};
+
+/// The share of a final hand category in the overall result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryShare {
+    /// The name of the category, `None` for non-paying hands.
+    pub category: Option<String>,
+    pub payout: usize,
+    pub probability: f64,
+    /// The part of the return stemming from this category.
+    pub contribution: f64,
+}
+
+/// The return, variance and hit frequency of a game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub paytable: String,
+    /// The fraction of all possible deals that was analysed.
+    pub coverage: f64,
+    /// The expected payout per coin bet.
+    pub expected_return: f64,
+    /// The variance of the payout per coin bet.
+    pub variance: f64,
+    /// The probability of a paying final hand.
+    pub hit_frequency: f64,
+    pub categories: Vec<CategoryShare>,
+}
+
+/// Sums up weighted outcomes.
+struct Accumulator {
+    weight: f64,
+    expected_return: f64,
+    second_moment: f64,
+    probabilities: Vec<f64>,
+}
+
+impl Accumulator {
+    fn new(table: &DrawTable) -> Self {
+        Self {
+            weight: 0f64,
+            expected_return: 0f64,
+            second_moment: 0f64,
+            probabilities: vec![0f64; table.paytable().payouts.len() + 1],
+        }
+    }
+
+    fn add(&mut self, table: &DrawTable, weight: f64, outcome: &Outcome, expected_value: f64) {
+        self.weight += weight;
+        self.expected_return += weight * expected_value;
+        self.second_moment += weight * outcome.second_moment(table.paytable());
+
+        for (total, probability) in self.probabilities.iter_mut().zip(outcome.probabilities()) {
+            *total += weight * probability;
+        }
+    }
+
+    fn finish(self, table: &DrawTable) -> Report {
+        let paytable = table.paytable();
+        let weight = self.weight;
+
+        let expected_return = self.expected_return / weight;
+        let probabilities: Vec<f64> = self.probabilities.iter().map(|probability| probability / weight).collect();
+
+        let mut categories: Vec<CategoryShare> = paytable.payouts
+            .iter()
+            .zip(&probabilities)
+            .map(|(payout, &probability)| CategoryShare {
+                category: Some(payout.category.to_string()),
+                payout: payout.payout,
+                probability,
+                contribution: probability * payout.payout as f64,
+            })
+            .collect();
+
+        categories.push(CategoryShare { category: None, payout: 0, probability: probabilities[paytable.payouts.len()], contribution: 0f64 });
+
+        Report {
+            paytable: paytable.name.clone(),
+            coverage: weight,
+            expected_return,
+            variance: self.second_moment / weight - expected_return * expected_return,
+            hit_frequency: 1f64 - probabilities[paytable.payouts.len()],
+            categories,
+        }
+    }
+}
+
+/// The probability of being dealt any hand of the suit-isomorphism class of the given hand.
+pub(crate) fn class_weight(table: &DrawTable, hand: &[Card; 5]) -> f64 {
+    canonical::suit_multiplicity(hand) as f64 / draw::binomial(table.deck().len(), 5) as f64
+}
+
+/// The canonical representatives of all suit-isomorphism classes of deals.
+pub(crate) fn classes(table: &DrawTable) -> Vec<[Card; 5]> {
+    let mut seen = HashSet::new();
+
+    table.deck()
+        .iter()
+        .copied()
+        .combinations(5)
+        .map(|hand| canonical::canonicalize(&<[Card; 5]>::try_from(hand).unwrap()))
+        .filter(|class| seen.insert(*class))
+        .collect()
+}
+
+impl Report {
+    /// Builds the report of the given computed moves, weighting each pattern by its probability (see [state::weigh]).
+    pub fn from_moves(table: &DrawTable, moves: &[ComputedMove]) -> Self {
+        let mut accumulator = Accumulator::new(table);
+        let mut mismatches = 0usize;
+
+        for (weight, r#move) in state::weigh(moves, table.deck().len()) {
+            let outcome = table.outcome(&r#move.pattern, &r#move.keep);
+
+            if (outcome.expected_value(table.paytable()) - r#move.average_score).abs() > 1e-9 {
+                mismatches += 1;
+            }
+
+            accumulator.add(table, weight, &outcome, r#move.average_score);
+        }
+
+        if mismatches != 0 {
+            log::warn!("The average score of {mismatches} moves does not match paytable `{}`.", table.paytable().name);
+        }
+
+        accumulator.finish(table)
+    }
+
+    /// Computes the optimal hold of every possible deal and builds the report of optimal play.
+    pub fn solve(table: &DrawTable) -> Self {
+        let mut accumulator = Accumulator::new(table);
+
+        for class in classes(table) {
+            let optimal = table.optimal(&class);
+            let outcome = table.outcome(&class, &optimal.keep);
+
+            accumulator.add(table, class_weight(table, &class), &outcome, optimal.average_score);
+        }
+
+        accumulator.finish(table)
+    }
+
+    /// Builds the exact report of playing every possible deal by the given policy.
+    pub fn from_policy(table: &DrawTable, policy: &impl Policy) -> Self {
+        let mut accumulator = Accumulator::new(table);
+
+        for class in classes(table) {
+            let outcome = table.outcome(&class, &policy.hold(&class));
+            let expected_value = outcome.expected_value(table.paytable());
+
+            accumulator.add(table, class_weight(table, &class), &outcome, expected_value);
+        }
+
+        accumulator.finish(table)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "Paytable:           {}", self.paytable)?;
+        writeln!(formatter, "Coverage:           {:.4}%", self.coverage * 100f64)?;
+        writeln!(formatter, "Return:             {:.4}%", self.expected_return * 100f64)?;
+        writeln!(formatter, "Variance:           {:.4}", self.variance)?;
+        writeln!(formatter, "Standard deviation: {:.4}", self.variance.sqrt())?;
+        writeln!(formatter, "Hit frequency:      {:.4}%", self.hit_frequency * 100f64)?;
+        writeln!(formatter)?;
+        writeln!(formatter, "{:<20} {:>6} {:>12} {:>12}", "Category", "Payout", "Probability", "Contribution")?;
+
+        for share in &self.categories {
+            writeln!(
+                formatter, "{:<20} {:>6} {:>12.8} {:>11.4}%",
+                share.category.as_deref().unwrap_or("Nothing"), share.payout, share.probability, share.contribution * 100f64
+            )?;
+        }
+
+        Ok(())
+    }
+}
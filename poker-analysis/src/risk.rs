@@ -0,0 +1,214 @@
+//! Variance and bankroll requirements of a game.
+
+use core::fmt;
+use std::collections::BTreeMap;
+
+use poker_base::ComputedMove;
+
+use crate::{draw::DrawTable, report::Report, state};
+
+/// The longest session whose risk of ruin is computed exactly; longer ones are approximated.
+pub const MAX_EXACT_GAMES: usize = 10_000;
+
+/// The distribution of the payout (per coin bet) of a single game.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PayoutDistribution {
+    /// The probability of each payout.
+    pub probabilities: BTreeMap<usize, f64>,
+}
+
+impl PayoutDistribution {
+    /// Combines the distributions of the computed moves, weighted by the probability of their pattern.
+    /// The distribution of moves computed without one (i.e. by older clients) is recomputed using the table.
+    pub fn from_moves(table: &DrawTable, moves: &[ComputedMove]) -> Self {
+        let mut probabilities: BTreeMap<usize, f64> = BTreeMap::new();
+        let mut total = 0f64;
+
+        for (weight, r#move) in state::weigh(moves, table.deck().len()) {
+            let distribution = match r#move.distribution.is_empty() {
+                true => table.outcome(&r#move.pattern, &r#move.keep).distribution(table.paytable()),
+                false => r#move.distribution.clone(),
+            };
+
+            let draws: u64 = distribution.values().sum();
+
+            for (payout, amount) in distribution {
+                *probabilities.entry(payout).or_default() += weight * amount as f64 / draws as f64;
+            }
+
+            total += weight;
+        }
+
+        for probability in probabilities.values_mut() {
+            *probability /= total;
+        }
+
+        Self { probabilities }
+    }
+
+    /// Extracts the distribution from the category shares of a report.
+    pub fn from_report(report: &Report) -> Self {
+        let mut probabilities: BTreeMap<usize, f64> = BTreeMap::new();
+
+        for share in &report.categories {
+            *probabilities.entry(share.payout).or_default() += share.probability;
+        }
+
+        Self { probabilities }
+    }
+
+    /// The expected payout per coin bet, i.e. the return.
+    pub fn mean(&self) -> f64 {
+        self.probabilities.iter().map(|(&payout, probability)| payout as f64 * probability).sum()
+    }
+
+    pub fn variance(&self) -> f64 {
+        let mean = self.mean();
+
+        self.probabilities.iter().map(|(&payout, probability)| (payout as f64 - mean).powi(2) * probability).sum()
+    }
+
+    pub fn standard_deviation(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// The amount of games after which the expected result equals one standard deviation of the
+    /// result, i.e. the point at which the edge starts to dominate the fluctuation.
+    pub fn n0(&self) -> f64 {
+        self.variance() / (self.mean() - 1f64).powi(2)
+    }
+
+    /// The probability of losing the whole bankroll (in coins) within the given amount of games,
+    /// betting one coin per game; an empty bankroll is ruined from the start. This is computed exactly, at a cost quadratic in the amount of games,
+    /// for up to [MAX_EXACT_GAMES] games and approximated by [diffusion_risk_of_ruin] for longer sessions.
+    pub fn risk_of_ruin(&self, bankroll: usize, games: usize) -> f64 {
+        if bankroll == 0 {
+            return 1f64;
+        }
+
+        if bankroll > games {
+            return 0f64;
+        }
+
+        if games > MAX_EXACT_GAMES {
+            return diffusion_risk_of_ruin(self.mean() - 1f64, self.variance(), bankroll as f64, games as f64);
+        }
+
+        // bankrolls above the amount of remaining games can no longer be lost, so none larger are tracked.
+        let mut bankrolls = vec![0f64; games + 1];
+        bankrolls[bankroll] = 1f64;
+
+        let mut ruin = 0f64;
+
+        for remaining in (1..=games).rev() {
+            let mut next = vec![0f64; games + 1];
+
+            for (current, &probability) in bankrolls.iter().enumerate().take(remaining + 1).skip(1) {
+                if probability == 0f64 {
+                    continue;
+                }
+
+                for (&payout, &chance) in &self.probabilities {
+                    let after = current - 1 + payout;
+
+                    if after == 0 {
+                        ruin += probability * chance;
+                    } else if after < remaining {
+                        next[after] += probability * chance;
+                    }
+                }
+            }
+
+            bankrolls = next;
+        }
+
+        ruin
+    }
+
+    /// The probability of ever losing the whole bankroll (in coins) when playing indefinitely.
+    /// This is certain unless the game has a positive expectation.
+    pub fn long_run_risk_of_ruin(&self, bankroll: usize) -> f64 {
+        if self.mean() <= 1f64 {
+            return 1f64;
+        }
+
+        // the ruin probability is r^bankroll, where r is the root of sum(p * r^(payout - 1)) = 1 below one.
+        let excess = |r: f64| self.probabilities.iter().map(|(&payout, p)| p * r.powi(payout as i32 - 1)).sum::<f64>() - 1f64;
+
+        let (mut low, mut high) = (1e-9, 1f64 - 1e-12);
+
+        for _ in 0..200 {
+            let middle = (low + high) / 2f64;
+
+            if excess(middle) > 0f64 {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        low.powi(bankroll as i32)
+    }
+}
+
+/// The risk figures of a game for a given bankroll and session length.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskReport {
+    pub expected_return: f64,
+    pub standard_deviation: f64,
+    pub n0: f64,
+    /// The bankroll in coins.
+    pub bankroll: usize,
+    /// The amount of games in the session.
+    pub games: usize,
+    pub risk_of_ruin: f64,
+    pub long_run_risk_of_ruin: f64,
+}
+
+impl RiskReport {
+    pub fn new(distribution: &PayoutDistribution, bankroll: usize, games: usize) -> Self {
+        Self {
+            expected_return: distribution.mean(),
+            standard_deviation: distribution.standard_deviation(),
+            n0: distribution.n0(),
+            bankroll,
+            games,
+            risk_of_ruin: distribution.risk_of_ruin(bankroll, games),
+            long_run_risk_of_ruin: distribution.long_run_risk_of_ruin(bankroll),
+        }
+    }
+}
+
+impl fmt::Display for RiskReport {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "Return:             {:.4}%", self.expected_return * 100f64)?;
+        writeln!(formatter, "Standard deviation: {:.4}", self.standard_deviation)?;
+        writeln!(formatter, "N0:                 {:.0} games", self.n0)?;
+        writeln!(formatter, "Risk of ruin:       {:.4}% ({} coins over {} games)", self.risk_of_ruin * 100f64, self.bankroll, self.games)?;
+        writeln!(formatter, "Long-run risk:      {:.4}%", self.long_run_risk_of_ruin * 100f64)
+    }
+}
+
+/// The standard normal cumulative distribution function, by the complementary error function approximation
+/// of Numerical Recipes (relative error below 1.2e-7).
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / 2f64.sqrt();
+    let t = 1f64 / (1f64 + x / 2f64);
+
+    let polynomial = -1.26551223 + t * (1.00002368 + t * (0.37409196 + t * (0.09678418 + t * (-0.18628806
+        + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let tail = t * (-x * x + polynomial).exp() / 2f64;
+
+    if z < 0f64 { tail } else { 1f64 - tail }
+}
+
+/// The probability of losing the bankroll within the given amount of deals, approximating the bankroll
+/// by a Brownian motion with the drift and variance of a deal (e.g. of several lines, see [crate::multiplay]).
+pub fn diffusion_risk_of_ruin(drift: f64, variance: f64, bankroll: f64, deals: f64) -> f64 {
+    let spread = (variance * deals).sqrt();
+
+    // the reflected path term, in logarithms as the exponential factor may overflow
+    let reflected = -2f64 * drift * bankroll / variance + normal_cdf((-bankroll + drift * deals) / spread).ln();
+
+    (normal_cdf((-bankroll - drift * deals) / spread) + reflected.exp()).min(1f64)
+}
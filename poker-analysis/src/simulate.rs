@@ -0,0 +1,242 @@
+//! Monte Carlo simulation of long-run play, to validate strategies empirically.
+
+use core::fmt;
+use std::thread;
+
+use poker_base::{Card, GameVariant, Paytable};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::policy::Policy;
+
+/// The z-score of a two-sided 95% confidence interval.
+pub const Z_95: f64 = 1.959964;
+/// The z-score of a two-sided 99% confidence interval.
+pub const Z_99: f64 = 2.575829;
+
+/// The settings of a simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    /// The amount of dealt hands.
+    pub hands: u64,
+    /// The amount of hands played from each dealt hand, as in multi-play games: the held cards are copied
+    /// to every line, each drawing from its own deck.
+    pub lines: usize,
+    pub seed: u64,
+    /// The amount of points of the bankroll trajectory, each taken after an equal share of the hands.
+    pub points: usize,
+}
+
+/// The bankroll after a number of dealt hands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrajectoryPoint {
+    pub hands: u64,
+    /// The net win in coins, betting one coin per line.
+    pub bankroll: i64,
+}
+
+/// The results of a simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    pub paytable: Paytable,
+    /// The amount of dealt hands.
+    pub hands: u64,
+    pub lines: usize,
+    /// The summed payouts (per coin bet).
+    pub payout: u64,
+    /// The summed squares of the payouts of each dealt hand (over all its lines).
+    pub squared_payout: u128,
+    /// For each category of the paytable, the amount of final hands (of all lines) in it. The last entry counts non-paying hands.
+    pub counts: Vec<u64>,
+    pub trajectory: Vec<TrajectoryPoint>,
+    /// The lowest bankroll reached at any time.
+    pub lowest: i64,
+}
+
+impl Simulation {
+    /// The amount of final hands, i.e. coins bet.
+    pub fn played(&self) -> u64 {
+        self.hands * self.lines as u64
+    }
+
+    /// The average payout per coin bet.
+    pub fn realised_return(&self) -> f64 {
+        self.payout as f64 / self.played() as f64
+    }
+
+    /// The sample variance of the payout of a dealt hand (over all its lines) per coin bet.
+    pub fn variance(&self) -> f64 {
+        let hands = self.hands as f64;
+        let lines = self.lines as f64;
+        let mean = self.realised_return() * lines;
+
+        (self.squared_payout as f64 - hands * mean * mean) / (hands - 1f64).max(1f64) / (lines * lines)
+    }
+
+    /// The standard error of the realised return.
+    pub fn standard_error(&self) -> f64 {
+        (self.variance() / self.hands as f64).sqrt()
+    }
+
+    /// The confidence interval of the return for the given z-score, e.g. [Z_95].
+    pub fn confidence_interval(&self, z: f64) -> (f64, f64) {
+        let (mean, error) = (self.realised_return(), self.standard_error());
+
+        (mean - z * error, mean + z * error)
+    }
+
+    /// By how many standard errors the realised return deviates from the expected one.
+    pub fn deviation(&self, expected_return: f64) -> f64 {
+        (self.realised_return() - expected_return) / self.standard_error()
+    }
+}
+
+impl fmt::Display for Simulation {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (low_95, high_95) = self.confidence_interval(Z_95);
+        let (low_99, high_99) = self.confidence_interval(Z_99);
+
+        writeln!(formatter, "Paytable:           {}", self.paytable.name)?;
+        writeln!(formatter, "Hands:              {} ({} lines each)", self.hands, self.lines)?;
+        writeln!(formatter, "Realised return:    {:.4}%", self.realised_return() * 100f64)?;
+        writeln!(formatter, "95% confidence:     {:.4}% - {:.4}%", low_95 * 100f64, high_95 * 100f64)?;
+        writeln!(formatter, "99% confidence:     {:.4}% - {:.4}%", low_99 * 100f64, high_99 * 100f64)?;
+        writeln!(formatter, "Standard deviation: {:.4}", self.variance().sqrt())?;
+        writeln!(formatter, "Lowest bankroll:    {} coins", self.lowest)?;
+        writeln!(formatter)?;
+        writeln!(formatter, "{:<20} {:>6} {:>12} {:>12}", "Category", "Payout", "Hands", "Frequency")?;
+
+        let categories = self.paytable.payouts.iter().map(|payout| (payout.category.to_string(), payout.payout)).chain([("Nothing".to_string(), 0)]);
+
+        for ((category, payout), &count) in categories.zip(&self.counts) {
+            writeln!(formatter, "{category:<20} {payout:>6} {count:>12} {:>11.6}%", count as f64 / self.played() as f64 * 100f64)?;
+        }
+
+        writeln!(formatter)?;
+        writeln!(formatter, "{:>12} {:>12}", "Hands", "Bankroll")?;
+
+        for point in &self.trajectory {
+            writeln!(formatter, "{:>12} {:>12}", point.hands, point.bankroll)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The results of a consecutive run of hands.
+struct Chunk {
+    payout: u64,
+    squared_payout: u128,
+    counts: Vec<u64>,
+    /// The net win over the chunk.
+    net: i64,
+    /// The lowest net win within the chunk.
+    lowest: i64,
+}
+
+/// Plays the hands of a chunk, dealing from a freshly shuffled deck for every hand and drawing each line
+/// from a freshly shuffled rest of the deck.
+fn play(game: &impl GameVariant, paytable: &Paytable, policy: &impl Policy, hands: u64, lines: usize, seed: u64) -> Chunk {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut deck = game.deck();
+
+    let mut chunk = Chunk { payout: 0, squared_payout: 0, counts: vec![0; paytable.payouts.len() + 1], net: 0, lowest: 0 };
+
+    for _ in 0..hands {
+        deck.partial_shuffle(&mut rng, 5);
+
+        let dealt: [Card; 5] = deck[deck.len() - 5..].try_into().unwrap();
+        let keep = policy.hold(&dealt);
+        let rest = deck.len() - 5;
+
+        let mut total = 0u64;
+
+        for _ in 0..lines {
+            let (replacements, _) = deck[..rest].partial_shuffle(&mut rng, 5 - keep.len());
+            let mut replacements = replacements.iter();
+
+            let drawn: Vec<Card> = (0..dealt.len())
+                .map(|index| match keep.contains(&index) {
+                    true => dealt[index],
+                    false => *replacements.next().unwrap(),
+                })
+                .collect();
+
+            let category = paytable.category(game.evaluate(drawn)).unwrap_or(paytable.payouts.len());
+
+            total += paytable.payouts.get(category).map_or(0, |payout| payout.payout) as u64;
+            chunk.counts[category] += 1;
+        }
+
+        chunk.payout += total;
+        chunk.squared_payout += (total as u128) * (total as u128);
+        chunk.net += total as i64 - lines as i64;
+        chunk.lowest = chunk.lowest.min(chunk.net);
+    }
+
+    chunk
+}
+
+/// Plays the given amount of hands by the policy. The hands are played in chunks, one per trajectory point,
+/// each with its own generator seeded from the seed. The chunks are spread over all available cores, yet the
+/// results only depend on the settings.
+pub fn simulate<G: GameVariant>(game: &G, paytable: &Paytable, policy: &impl Policy, settings: Settings) -> Simulation {
+    let points = settings.points.clamp(1, settings.hands.max(1) as usize);
+
+    let mut master = StdRng::seed_from_u64(settings.seed);
+    let chunks: Vec<(u64, u64)> = (0..points as u64)
+        .map(|index| (settings.hands / points as u64 + (index < settings.hands % points as u64) as u64, master.gen()))
+        .collect();
+
+    let cpus = thread::available_parallelism().map(|parallelism| parallelism.get()).unwrap_or(1);
+
+    let mut results: Vec<(usize, Chunk)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..cpus)
+            .map(|cpu| {
+                let chunks = &chunks;
+
+                scope.spawn(move || {
+                    chunks.iter()
+                        .enumerate()
+                        .skip(cpu)
+                        .step_by(cpus)
+                        .map(|(index, &(hands, seed))| (index, play(game, paytable, policy, hands, settings.lines, seed)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut simulation = Simulation {
+        paytable: paytable.clone(),
+        hands: 0,
+        lines: settings.lines,
+        payout: 0,
+        squared_payout: 0,
+        counts: vec![0; paytable.payouts.len() + 1],
+        trajectory: Vec::with_capacity(points),
+        lowest: 0,
+    };
+
+    let mut bankroll = 0i64;
+
+    for ((hands, _), (_, chunk)) in chunks.iter().zip(results) {
+        simulation.lowest = simulation.lowest.min(bankroll + chunk.lowest);
+        bankroll += chunk.net;
+
+        simulation.hands += hands;
+        simulation.payout += chunk.payout;
+        simulation.squared_payout += chunk.squared_payout;
+
+        for (total, count) in simulation.counts.iter_mut().zip(chunk.counts) {
+            *total += count;
+        }
+
+        simulation.trajectory.push(TrajectoryPoint { hands: simulation.hands, bankroll });
+    }
+
+    simulation
+}
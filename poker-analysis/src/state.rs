@@ -0,0 +1,60 @@
+use std::{collections::HashMap, error::Error, fs, path::Path};
+
+use serde::Deserialize;
+
+use poker_base::{canonical, Card, ComputedMove, Variant};
+
+use crate::draw::binomial;
+
+/// The part of the server's state file that holds the computed moves.
+#[derive(Debug, Clone, Deserialize)]
+pub struct State {
+    #[serde(default)]
+    pub variant: Variant,
+    pub computed: Vec<ComputedMove>,
+}
+
+/// Loads the state file written by the server.
+pub fn load(file: impl AsRef<Path>) -> Result<State, Box<dyn Error>> {
+    Ok(serde_json::from_str(&fs::read_to_string(file)?)?)
+}
+
+/// Loads the computed moves from the state file written by the server.
+pub fn load_moves(file: impl AsRef<Path>) -> Result<Vec<ComputedMove>, Box<dyn Error>> {
+    Ok(load(file)?.computed)
+}
+
+/// Loads the computed moves of the state file, checking that they were computed for the given variant.
+pub fn load_variant_moves(file: impl AsRef<Path>, variant: Variant) -> Result<Vec<ComputedMove>, Box<dyn Error>> {
+    log::info!("Loading computed moves from `{}`...", file.as_ref().display());
+    let state = load(file)?;
+    log::info!("Loaded {} computed moves of `{}`.", state.computed.len(), state.variant);
+
+    if state.variant != variant {
+        return Err(format!("The state file computes `{}`, not `{variant}`.", state.variant).into());
+    }
+
+    Ok(state.computed)
+}
+
+/// Weights each move by the probability of being dealt its pattern from a deck of the given size.
+/// Suit-isomorphic patterns share the weight of their class, so both full and canonicalised
+/// sets of patterns are weighted correctly. The weights add up to the covered fraction of all deals.
+pub fn weigh(moves: &[ComputedMove], deck_size: usize) -> Vec<(f64, &ComputedMove)> {
+    let mut classes: HashMap<[Card; 5], Vec<&ComputedMove>> = HashMap::new();
+
+    for r#move in moves {
+        classes.entry(canonical::canonicalize(&r#move.pattern)).or_default().push(r#move);
+    }
+
+    let deals = binomial(deck_size, 5) as f64;
+
+    classes
+        .into_iter()
+        .flat_map(|(class, moves)| {
+            let weight = canonical::suit_multiplicity(&class) as f64 / deals / moves.len() as f64;
+
+            moves.into_iter().map(move |r#move| (weight, r#move))
+        })
+        .collect()
+}
@@ -0,0 +1,49 @@
+//! Looking up the computed hold of any dealt hand.
+
+use std::collections::HashMap;
+
+use poker_base::{canonical, Card, ComputedMove};
+
+/// The computed moves, indexed by the suit-isomorphism class of their pattern. Any hand of a class can be
+/// looked up, so both full and canonicalised sets of patterns serve as a strategy.
+pub struct Strategy {
+    moves: HashMap<[Card; 5], ComputedMove>,
+}
+
+impl Strategy {
+    /// Indexes the moves. Of several moves of the same class, the first one is used.
+    pub fn new(moves: impl IntoIterator<Item = ComputedMove>) -> Self {
+        let mut indexed = HashMap::new();
+
+        for r#move in moves {
+            indexed.entry(canonical::canonicalize(&r#move.pattern)).or_insert(r#move);
+        }
+
+        Self { moves: indexed }
+    }
+
+    /// The amount of covered classes.
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// The computed move of the hand's class, translated to the hand: its pattern is the hand and
+    /// the kept indices refer to the hand's cards. `None` if the class was not computed.
+    pub fn lookup(&self, hand: &[Card; 5]) -> Option<ComputedMove> {
+        let r#move = self.moves.get(&canonical::canonicalize(hand))?;
+        let permutation = canonical::isomorphism(&r#move.pattern, hand)?;
+
+        let mut keep: Vec<usize> = r#move.keep
+            .iter()
+            .map(|&index| canonical::permute_card(r#move.pattern[index], &permutation))
+            .map(|card| hand.iter().position(|&dealt| dealt == card).expect("isomorphic hands contain the permuted cards"))
+            .collect();
+        keep.sort_unstable();
+
+        Some(ComputedMove { pattern: *hand, keep, ..r#move.clone() })
+    }
+}
@@ -0,0 +1,258 @@
+//! Automatic synthesis of compact hold-priority lists (see [crate::priority]) from the optimal holds.
+//!
+//! Every hold is described by the rules of its features: the made hand or draw, the amount of high
+//! cards, suitedness, gaps and penalty cards. The rules describing an optimal hold somewhere are the
+//! candidate lines. Lines are added greedily, each one kept at the position given by the average value
+//! of the holds it describes, as long as the list grows more valuable and does not exceed the maximum
+//! length. Neighbouring lines are then swapped as long as that improves the list further.
+
+use std::{collections::HashMap, error::Error};
+
+use itertools::Itertools;
+use poker_base::{Card, Value};
+
+use crate::{
+    draw::DrawTable, hold::{self, HoldClass, HoldKind}, policy::Policy,
+    priority::{PriorityList, Rule, Shape}, report,
+};
+
+/// Improvements of the return below are not worth a line.
+const MIN_GAIN: f64 = 1e-9;
+/// The most passes of swapping neighbouring lines.
+const SWAP_PASSES: usize = 10;
+
+/// The rules describing the features of the hold of the given cards (classified as `class`). Each rule
+/// matches the hold, and a hold matches each of these rules exactly if it is described by it.
+pub fn features(class: &HoldClass, cards: &[Card]) -> Vec<Rule> {
+    if cards.is_empty() {
+        return vec![Rule { cards: Some(0), ..Rule::new(Shape::DiscardAll) }];
+    }
+
+    if let HoldKind::Made(made) = class.kind {
+        return vec![Rule::new(Shape::Made(made))];
+    }
+
+    let values: Vec<Value> = cards.iter().map(|card| card.value).sorted().rev().collect();
+
+    let natural = cards.iter().all(|card| !card.is_joker());
+    let distinct = values.iter().all_unique();
+    let suited = natural && hold::is_suited(cards);
+    let straight = match natural && distinct {
+        true => hold::straight_ranks(&values),
+        false => None,
+    };
+
+    let n = cards.len();
+    let high_cards = values.iter().filter(|&&value| value != Value::Joker && hold::is_high(value)).count();
+    // suitedness only distinguishes several cards
+    let suitedness = (n > 1).then_some(suited);
+
+    let mut shapes = vec![Rule::new(Shape::Values { values: values.clone(), suited: suitedness })];
+
+    if suited && n >= 2 && values.iter().all(|&value| value >= Value::Ten) {
+        shapes.push(Rule { cards: Some(n), ..Rule::new(Shape::RoyalFlushDraw) });
+    }
+
+    if let (true, true, Some(ranks)) = (suited, n >= 3, &straight) {
+        shapes.push(Rule { cards: Some(n), gaps: Some(hold::gaps(ranks)), high_cards: Some(high_cards), ..Rule::new(Shape::StraightFlushDraw) });
+    }
+
+    if suited && n >= 3 {
+        shapes.push(Rule { cards: Some(n), high_cards: Some(high_cards), ..Rule::new(Shape::FlushDraw) });
+    }
+
+    if let (true, Some(ranks)) = (n >= 3, &straight) {
+        let outside = Some(hold::is_outside(ranks));
+
+        shapes.push(Rule { cards: Some(n), high_cards: Some(high_cards), ..Rule::new(Shape::StraightDraw { outside }) });
+    }
+
+    if natural && distinct && high_cards == n {
+        shapes.push(Rule { cards: Some(n), ..Rule::new(Shape::HighCards { suited: suitedness }) });
+    }
+
+    // each shape with and without its penalties
+    shapes
+        .into_iter()
+        .flat_map(|rule| {
+            let penalised = Rule { flush_penalty: Some(class.penalties.flush), straight_penalty: Some(class.penalties.straight), ..rule.clone() };
+
+            [rule, penalised]
+        })
+        .collect()
+}
+
+/// The features of all 32 holds of the hand, in the order of [DrawTable::outcomes].
+fn hold_features(table: &DrawTable, hand: &[Card; 5]) -> Vec<Vec<Rule>> {
+    (0..hand.len())
+        .powerset()
+        .map(|keep| {
+            let cards: Vec<Card> = keep.iter().map(|&index| hand[index]).collect();
+
+            features(&hold::classify(hand, &keep, |card| table.is_wild(card)), &cards)
+        })
+        .collect()
+}
+
+/// A candidate line.
+struct Candidate {
+    rule: Rule,
+    /// The average expected value of the optimal holds it describes.
+    priority: f64,
+    /// The hands any hold matches the rule, with the expected value of the first such hold.
+    postings: Vec<(usize, f64)>,
+}
+
+/// A synthesised hold-priority list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Synthesis {
+    pub list: PriorityList,
+    /// The return of optimal play.
+    pub optimal_return: f64,
+    /// The return of playing by the list.
+    pub list_return: f64,
+}
+
+/// Learns a hold-priority list of at most `max_lines` lines (the last one discarding everything) that
+/// approaches the return of the optimal policy (e.g. [crate::policy::Optimal]). The list needs at least one line.
+pub fn synthesize(table: &DrawTable, optimal: &impl Policy, max_lines: usize) -> Result<Synthesis, Box<dyn Error>> {
+    if max_lines == 0 {
+        return Err("A priority list needs at least one line.".into());
+    }
+
+    let paytable = table.paytable();
+    let hands = report::classes(table);
+    let weights: Vec<f64> = hands.iter().map(|hand| report::class_weight(table, hand)).collect();
+    let holds: Vec<Vec<usize>> = (0..5).powerset().collect();
+
+    log::info!("Collecting the features of the optimal holds of {} hands...", hands.len());
+
+    let mut values: Vec<Vec<f64>> = Vec::with_capacity(hands.len());
+    let mut optimal_return = 0f64;
+    let mut totals: HashMap<Rule, (f64, f64)> = HashMap::new();
+
+    for (hand, &weight) in hands.iter().zip(&weights) {
+        let evs: Vec<f64> = table.outcomes(hand).iter().map(|outcome| outcome.expected_value(paytable)).collect();
+
+        let keep = optimal.hold(hand);
+        let index = holds.iter().position(|hold| *hold == keep).expect("holds are sorted indices");
+        let cards: Vec<Card> = keep.iter().map(|&index| hand[index]).collect();
+
+        optimal_return += weight * evs[index];
+
+        for rule in features(&hold::classify(hand, &keep, |card| table.is_wild(card)), &cards) {
+            let (value, total) = totals.entry(rule).or_default();
+
+            *value += weight * evs[index];
+            *total += weight;
+        }
+
+        values.push(evs);
+    }
+
+    let mut candidates: Vec<Candidate> = totals
+        .into_iter()
+        .map(|(rule, (value, total))| Candidate { rule, priority: value / total, postings: Vec::new() })
+        .collect();
+
+    // the order of the lines, ties broken by the rule's notation to be deterministic
+    candidates.sort_by(|a, b| b.priority.total_cmp(&a.priority).then_with(|| a.rule.to_string().cmp(&b.rule.to_string())));
+
+    let ids: HashMap<Rule, usize> = candidates.iter().enumerate().map(|(id, candidate)| (candidate.rule.clone(), id)).collect();
+
+    log::info!("Matching {} candidate lines...", candidates.len());
+
+    for (index, hand) in hands.iter().enumerate() {
+        let mut seen = vec![false; candidates.len()];
+
+        for (features, &value) in hold_features(table, hand).iter().zip(&values[index]) {
+            for rule in features {
+                if let Some(&id) = ids.get(rule) {
+                    if !seen[id] {
+                        seen[id] = true;
+                        candidates[id].postings.push((index, value));
+                    }
+                }
+            }
+        }
+    }
+
+    // without any line, every hand is discarded
+    let discard: Vec<f64> = values.iter().map(|values| values[0]).collect();
+
+    let mut lines: Vec<usize> = Vec::new();
+    let mut current: Vec<(usize, f64)> = discard.iter().map(|&value| (usize::MAX, value)).collect();
+
+    log::info!("Selecting lines...");
+
+    while lines.len() + 1 < max_lines {
+        let best = candidates
+            .iter()
+            .enumerate()
+            .filter(|(id, _)| !lines.contains(id))
+            .map(|(id, candidate)| {
+                let gain: f64 = candidate.postings
+                    .iter()
+                    .filter(|(hand, _)| id < current[*hand].0)
+                    .map(|&(hand, value)| weights[hand] * (value - current[hand].1))
+                    .sum();
+
+                (id, gain)
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        let Some((id, gain)) = best.filter(|(_, gain)| *gain > MIN_GAIN) else { break };
+
+        for &(hand, value) in &candidates[id].postings {
+            if id < current[hand].0 {
+                current[hand] = (id, value);
+            }
+        }
+
+        lines.push(id);
+        log::info!("Added `{}`, gaining {gain:.6}.", candidates[id].rule);
+    }
+
+    lines.sort_unstable();
+
+    let evaluate = |lines: &[usize]| -> f64 {
+        let mut chosen: Vec<Option<f64>> = vec![None; hands.len()];
+
+        for &id in lines {
+            for &(hand, value) in &candidates[id].postings {
+                chosen[hand].get_or_insert(value);
+            }
+        }
+
+        chosen.iter().zip(&discard).zip(&weights).map(|((chosen, discard), weight)| weight * chosen.unwrap_or(*discard)).sum()
+    };
+
+    let mut list_return = evaluate(&lines);
+
+    for _ in 0..SWAP_PASSES {
+        let mut improved = false;
+
+        for index in 1..lines.len() {
+            lines.swap(index - 1, index);
+
+            let swapped = evaluate(&lines);
+
+            match swapped > list_return + MIN_GAIN {
+                true => {
+                    list_return = swapped;
+                    improved = true;
+                },
+                false => lines.swap(index - 1, index),
+            }
+        }
+
+        if !improved {
+            break;
+        }
+    }
+
+    let mut rules: Vec<Rule> = lines.into_iter().map(|id| candidates[id].rule.clone()).collect();
+    rules.push(Rule { cards: Some(0), ..Rule::new(Shape::DiscardAll) });
+
+    Ok(Synthesis { list: PriorityList { rules }, optimal_return, list_return })
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+
+use poker_analysis::{diff::{ChartDiff, Diff}, export::{self, Chart, Format}};
+use poker_base::{parse_hand, ComputedMove, Variant};
+
+fn computed(cards: &str, keep: &[usize], average_score: f64) -> ComputedMove {
+    let pattern = parse_hand(cards).unwrap();
+
+    ComputedMove { pattern, keep: keep.to_vec(), average_score, distribution: BTreeMap::new() }
+}
+
+/// Patterns are matched across suit-isomorphic hands, a changed hold is reported in the old hand's
+/// terms and only the EV deltas of the compared patterns make up the return change.
+#[test]
+fn diff_reports_changed_holds() {
+    let old = [
+        computed("Ah Kh Qh Jh 2c", &[0, 1, 2, 3], 18.0),
+        computed("Ac Kd 7h 4s 2c", &[0, 1], 0.45),
+        computed("Jc Jd 7h 4s 2c", &[0, 1], 1.5),
+    ];
+    let new = [
+        computed("2d As Ks Qs Js", &[1, 2, 3, 4], 19.0),
+        computed("Ad Kc 7s 4h 2d", &[0], 0.47),
+    ];
+
+    let diff = Diff::compare(&Variant::JacksOrBetter, &old, &new);
+
+    assert!(diff.compared < diff.old_coverage);
+    assert_eq!(diff.changes.len(), 1);
+    assert_eq!(diff.changes[0].hand, old[1].pattern);
+    assert_eq!((diff.changes[0].old_keep.clone(), diff.changes[0].new_keep.clone()), (vec![0, 1], vec![0]));
+
+    assert_eq!(diff.classes.len(), 2);
+    assert_eq!(diff.classes.iter().filter(|class| class.hold_changed()).count(), 1);
+
+    let expected: f64 = diff.classes.iter().map(|class| class.probability * class.delta()).sum();
+    assert!((diff.return_change() - expected).abs() < 1e-15);
+    assert!(diff.return_change() > 0f64);
+}
+
+/// A strategy without any computed patterns has no return to show.
+#[test]
+fn empty_strategies_are_reported() {
+    let new = [computed("Ah Kh Qh Jh 2c", &[0, 1, 2, 3], 18.0)];
+
+    let diff = Diff::compare(&Variant::JacksOrBetter, &[], &new);
+
+    assert_eq!((diff.old_coverage, diff.compared), (0f64, 0f64));
+    assert!(diff.classes.is_empty() && diff.changes.is_empty());
+
+    let report = diff.to_string();
+    assert!(report.contains("Old return:         - (0.0000% of deals)"), "{report}");
+    assert!(!report.contains("NaN"), "{report}");
+}
+
+/// Holding other cards of the same expected value is no change of the strategy.
+#[test]
+fn equally_good_holds_are_unchanged() {
+    let old = [computed("Ah Ad As Ac Kc", &[0, 1, 2, 3], 25.0)];
+    let new = [computed("Ah Ad As Ac Kc", &[0, 1, 2, 3, 4], 25.0)];
+
+    let diff = Diff::compare(&Variant::JacksOrBetter, &old, &new);
+
+    assert!(diff.compared > 0f64);
+    assert!(diff.changes.is_empty() && diff.classes.is_empty());
+}
+
+/// Exported charts are read back and compared hold class by hold class.
+#[test]
+fn charts_are_compared_by_hold() {
+    let old = [computed("Ah Kh Qh Jh 2c", &[0, 1, 2, 3], 18.0), computed("Jc Jd 7h 4s 2c", &[0, 1], 1.5)];
+    let new = [computed("Ah Kh Qh Jh 2c", &[0, 1, 2, 3], 19.0), computed("Jc Jd 7h 4s 2c", &[0, 1], 1.5)];
+
+    for format in [Format::Csv, Format::Markdown] {
+        let read = |moves: &[_]| export::parse(&Chart::from_moves(moves, |_| false).render(format).unwrap(), format).unwrap();
+        let (old, new) = (read(&old), read(&new));
+
+        assert_eq!(old.len(), 2);
+        assert!(ChartDiff::compare(&old, &old).changes.is_empty());
+
+        let diff = ChartDiff::compare(&old, &new);
+
+        assert_eq!(diff.changes.len(), 1, "{format:?}");
+        assert_eq!(diff.changes[0].hold, Chart::from_moves(&[computed("Ah Kh Qh Jh 2c", &[0, 1, 2, 3], 18.0)], |_| false).entries[0].class.to_string());
+        assert!((diff.changes[0].delta().unwrap() - 1.0).abs() < 1e-9);
+
+        let removed = ChartDiff::compare(&old, &new[..1]);
+        assert!(removed.changes.iter().any(|change| change.old.is_some() && change.new.is_none()));
+    }
+
+    assert!(export::parse("<table>", Format::Html).is_err());
+    assert!(export::parse("priority,hold,hands\n1,\"a, b\",3", Format::Csv).unwrap_err().contains("expected 6 fields"));
+}
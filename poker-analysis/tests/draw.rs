@@ -0,0 +1,18 @@
+use poker_analysis::draw::DrawTable;
+use poker_base::{parse_hand, Deck, Paytable, Variant};
+use poker_strategy::video_poker;
+
+/// Keeping the quads and keeping the whole hand both pay 25: the table and the clients hold the fewest cards.
+#[test]
+fn tied_holds_are_broken_like_the_clients() {
+    let paytable = Paytable::jacks_or_better_9_6();
+    let table = DrawTable::new(Variant::JacksOrBetter, &paytable);
+    let hand = parse_hand("Ah Ad Kc As Ac").unwrap();
+
+    let optimal = table.optimal(&hand);
+    let computed = video_poker::calculate_optimal(&Variant::JacksOrBetter, &paytable, &Deck::standard().remaining(&hand), &hand);
+
+    assert_eq!(optimal.keep, vec![0, 1, 3, 4]);
+    assert_eq!(computed.keep, optimal.keep);
+    assert_eq!(optimal.average_score, 25f64);
+}
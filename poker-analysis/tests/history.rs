@@ -0,0 +1,62 @@
+use poker_analysis::{draw::DrawTable, history::{Audit, History}, policy::Optimal};
+use poker_base::{GameVariant, Paytable, Variant};
+
+const HISTORY: &str = "
+# dealt          | held  | final          | payout
+Ah Kh 7c 4d 2s   | Ah Kh | Ah Kh Qd 9c 3s | 0
+Jc Jd 7h 4s 2c   | Jc Jd | Jc Jd 7d 8s 9c | 1
+Ac Kd 7h 4s 2c   | -     | 3c 5d 6h 8s 9c | 0
+Ts Js Qs 3d 3c   | 3d 3c | 3d 3c 3h 9s 2c | 5
+";
+
+/// The audit finds the suboptimal holds and the hand paid more than the paytable's three for trips.
+#[test]
+fn audit_finds_mistakes_and_mismatched_payouts() {
+    let history: History = HISTORY.parse().unwrap();
+
+    assert_eq!(history.records.len(), 4);
+    assert_eq!(history.records[2].keep, Vec::<usize>::new());
+    assert_eq!(history.records[3].line, 6);
+
+    let paytable = Paytable::jacks_or_better_9_6();
+    let table = DrawTable::new(Variant::JacksOrBetter, &paytable);
+    let audit = Audit::new(&Variant::JacksOrBetter, &table, &history, &Optimal(&table));
+
+    assert_eq!(audit.mistakes.iter().map(|mistake| mistake.line).collect::<Vec<_>>(), vec![5, 6]);
+    assert_eq!(audit.mistakes[1].optimal, vec![0, 1, 2]);
+    assert!((audit.lost() - audit.mistakes.iter().map(|mistake| mistake.loss).sum::<f64>()).abs() < 1e-12);
+    assert_eq!(audit.mistake_classes.len(), 2);
+
+    assert_eq!(audit.mismatches.len(), 1);
+    assert_eq!((audit.mismatches[0].line, audit.mismatches[0].observed, audit.mismatches[0].expected), (6, 5, 3));
+}
+
+/// Held cards must have been dealt and kept in the final hand.
+#[test]
+fn history_rejects_impossible_holds() {
+    let error = "Ah Kh 7c 4d 2s | Qh | Qh Kh Qd 9c 3s | 0".parse::<History>().unwrap_err();
+    assert_eq!(error.line, 1);
+
+    let error = "\nAh Kh 7c 4d 2s | Ah Kh | Ah Qd 9c 3s 4s | 0".parse::<History>().unwrap_err();
+    assert_eq!(error.line, 2);
+
+    assert!("Ah Kh 7c 4d 2s | Ah Kh | Ah Kh Qd 9c 3s".parse::<History>().is_err());
+
+    let error = "Ah Kh 7c 4d 2s | Ah Kh | Ah Kh 7c 9c 3s | 0".parse::<History>().unwrap_err();
+    assert_eq!(error.reason, "the discarded 7♣ was drawn again");
+}
+
+/// Jokers are only dealt in Joker Poker.
+#[test]
+fn history_uses_the_deck_of_the_game() {
+    let record = "Ah Kh X 4d 2s | Ah Kh X | Ah Kh X Qh Jh | 800";
+
+    assert_eq!(History::parse(record, &Variant::JokerPoker.deck()).unwrap().records[0].keep, vec![0, 1, 2]);
+
+    for variant in [Variant::JacksOrBetter, Variant::DeucesWild] {
+        let error = History::parse(record, &variant.deck()).unwrap_err();
+
+        assert_eq!(error.line, 1);
+        assert!(error.reason.contains("not part of the deck"), "{}", error.reason);
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use poker_analysis::{export::Chart, hold::{self, HoldKind, MadeHand}};
+use poker_base::{parse_deck_hand, Card, ComputedMove, Deck, GameVariant, Variant};
+
+fn hand(cards: &str) -> [Card; 5] {
+    parse_deck_hand(cards, &Deck::with_joker().cards).unwrap()
+}
+
+/// Five kept cards with a joker are classified as the hand the joker completes.
+#[test]
+fn jokers_complete_made_hands() {
+    let kind = |cards: &str| hold::classify(&hand(cards), &[0, 1, 2, 3, 4], |card| Variant::JokerPoker.is_wild(card)).kind;
+
+    assert_eq!(kind("Ah Kh Qh Jh X"), HoldKind::Made(MadeHand::RoyalFlush));
+    assert_eq!(kind("9c 9d 9s 9h X"), HoldKind::Made(MadeHand::FiveOfAKind));
+    assert_eq!(kind("2c 7d 9s Kh X"), HoldKind::Made(MadeHand::HighPair));
+}
+
+fn wild_class(game: Variant, cards: &str, kept: &[usize]) -> String {
+    hold::classify(&hand(cards), kept, |card| game.is_wild(card)).to_string()
+}
+
+fn class(cards: &str, kept: &[usize]) -> String {
+    wild_class(Variant::JacksOrBetter, cards, kept)
+}
+
+/// Kept wild cards complete sets and are named alongside the draw of the natural cards.
+#[test]
+fn wild_cards_are_classified_by_game() {
+    assert_eq!(wild_class(Variant::JokerPoker, "X Ah Kh Qh 7c", &[0, 1, 2, 3]), "1 wild card + 3 to a royal flush");
+    assert_eq!(wild_class(Variant::JokerPoker, "X Kd 8c 6s 3h", &[0, 1]), "1 wild card + single K");
+    assert_eq!(wild_class(Variant::JokerPoker, "X Kd Kc 6s 3h", &[0, 1, 2]), "1 wild card + three of a kind");
+
+    assert_eq!(wild_class(Variant::DeucesWild, "2c Kh Qh Jh 7d", &[0, 1, 2, 3]), "1 wild card + 3 to a royal flush");
+    assert_eq!(wild_class(Variant::DeucesWild, "2c 2d 7h 7s Kd", &[0, 1, 2, 3]), "2 wild cards + four of a kind");
+    assert_eq!(wild_class(Variant::DeucesWild, "2c 2d 9h 6s Kd", &[0, 1]), "2 wild cards");
+    assert_eq!(wild_class(Variant::DeucesWild, "2c 2d 2h 2s Kd", &[0, 1, 2, 3]), "4 wild cards");
+
+    // deuces are only wild in Deuces Wild.
+    assert_eq!(class("2c 2d 9h 6s Kd", &[0, 1]), "Low pair");
+}
+
+#[test]
+fn holds_are_classified() {
+    assert_eq!(class("Jh Js 2c 5d 9s", &[0, 1]), "High pair");
+    assert_eq!(class("3h 3s 9c 9d Ks", &[0, 1, 2, 3]), "Two pair");
+    assert_eq!(class("Ah Kh Qh 2c 3d", &[0, 1, 2]), "3 to a royal flush");
+    assert_eq!(class("Kh 2h 5h 9h 3c", &[0, 1, 2, 3]), "4 to a flush (1 high card)");
+    assert_eq!(class("5h 6c 7d 8s Kc", &[0, 1, 2, 3]), "4 to an outside straight");
+    assert_eq!(class("5h 6c 8d 9s Kc", &[0, 1, 2, 3]), "4 to an inside straight");
+    assert_eq!(class("Kh Qc Jd 2s 5c", &[0, 1, 2]), "Unsuited KQJ");
+    assert_eq!(class("Kh Qh 3d 2s 5c", &[0, 1]), "2 to a royal flush");
+    assert_eq!(class("Kh Qc Jd 2s 5c", &[]), "Discard everything");
+}
+
+#[test]
+fn penalties_are_classified() {
+    assert_eq!(class("Ah Kh Qh Jc 9d", &[0, 1, 2]), "3 to a royal flush with straight penalty");
+    assert_eq!(class("Kh Qh 2h 7c 4d", &[0, 1]), "2 to a royal flush with flush penalty");
+    assert_eq!(class("Kh Qh 2h Jc 4d", &[0, 1]), "2 to a royal flush with flush and straight penalty");
+    assert_eq!(class("5h 6h 7h 8c Kd", &[0, 1, 2]), "3 to a straight flush, open with straight penalty");
+}
+
+/// A straight (flush) draw that can only be completed at one end has as few outs as an inside draw.
+#[test]
+fn one_ended_straight_draws_are_gapped() {
+    assert_eq!(class("5h 6h 7h 8h Kc", &[0, 1, 2, 3]), "4 to a straight flush, open");
+    assert_eq!(class("5h 6h 8h 9h Kc", &[0, 1, 2, 3]), "4 to a straight flush, 1 gap");
+    assert_eq!(class("Ah 2h 3h 4h 9c", &[0, 1, 2, 3]), "4 to a straight flush, 1 gap (1 high card)");
+    assert_eq!(class("Ah 2h 4h 9c Kd", &[0, 1, 2]), "3 to a straight flush, 2 gaps (1 high card)");
+    assert_eq!(class("Ac 2d 3h 4s 9c", &[0, 1, 2, 3]), "4 to an inside straight (1 high card)");
+    assert_eq!(class("Jc Qd Kh As 2c", &[0, 1, 2, 3]), "4 to an inside straight (4 high cards)");
+}
+
+/// Charts are ordered by which hold is kept over which, not by the average score of the holds.
+#[test]
+fn charts_are_ordered_by_hold_priority() {
+    let computed = |cards: &str, keep: Vec<usize>, average_score: f64| ComputedMove {
+        pattern: hand(cards),
+        keep,
+        average_score,
+        distribution: BTreeMap::new(),
+    };
+
+    let moves = [
+        // a high pair is held over four to a flush ...
+        computed("Jh Js 2h 5h 9h", vec![0, 1], 1.5),
+        // ... but pays less on average, as most high pairs come without a flush draw.
+        computed("Jh Jd 2c 5d 9s", vec![0, 1], 0.5),
+        computed("Jc Qd 2c 7d 4s", vec![0, 1], 0.5),
+        computed("Kh 2h 5h 9h 3c", vec![0, 1, 2, 3], 1.2),
+    ];
+
+    let chart = Chart::from_moves(&moves, |_| false);
+    let order: Vec<String> = chart.entries.iter().map(|entry| entry.class.to_string()).collect();
+
+    assert_eq!(order, vec!["High pair", "4 to a flush (1 high card)", "Unsuited QJ"]);
+    assert_eq!((chart.entries[0].patterns, chart.entries[0].average_score), (2, 1.0));
+    assert_eq!((chart.entries[0].min_score, chart.entries[0].max_score), (0.5, 1.5));
+}
@@ -0,0 +1,40 @@
+use poker_analysis::{
+    draw::DrawTable, multiplay::{self, Moments, MultiPlayReport}, policy::Optimal, risk,
+};
+use poker_base::{Paytable, Variant};
+
+/// Full-pay Jacks or Better has a variance of 19.5147 on a single line, the lines of a deal are
+/// correlated by about 0.1, which leaves ten-play with a deviation of 1.9290 per coin.
+#[test]
+fn multi_play_variance() {
+    let paytable = Paytable::jacks_or_better_9_6();
+    let table = DrawTable::new(Variant::JacksOrBetter, &paytable);
+
+    let moments = Moments::from_policy(&table, &Optimal(&table));
+
+    assert!((moments.mean - 0.995439).abs() < 1e-6, "return was {}", moments.mean);
+    assert!((moments.variance() - 19.5147).abs() < 1e-3, "variance was {}", moments.variance());
+    assert!(moments.covariance() > 0f64 && moments.covariance() < moments.variance() / 5f64);
+
+    let report = MultiPlayReport::new(moments, 10_000);
+
+    assert_eq!(report.rows.len(), multiplay::LINES.len());
+    assert!(report.rows.windows(2).all(|rows| rows[0].coin_deviation > rows[1].coin_deviation));
+    assert!(report.rows.iter().all(|row| row.bankrolls[0] < row.bankrolls[1]));
+
+    let ten_play = report.rows.iter().find(|row| row.lines == 10).unwrap();
+
+    assert!((ten_play.coin_deviation - 1.9290).abs() < 1e-4, "deviation was {}", ten_play.coin_deviation);
+}
+
+/// The approximated risk of ruin falls as the bankroll grows, and the requirement meets the risk.
+#[test]
+fn bankroll_requirement_meets_risk() {
+    let (drift, variance, deals) = (-0.05, 19.5, 10_000f64);
+
+    assert!(risk::diffusion_risk_of_ruin(drift, variance, 100f64, deals) > risk::diffusion_risk_of_ruin(drift, variance, 500f64, deals));
+
+    let bankroll = multiplay::bankroll_requirement(drift, variance, deals, 0.05);
+
+    assert!((risk::diffusion_risk_of_ruin(drift, variance, bankroll, deals) - 0.05).abs() < 1e-6);
+}
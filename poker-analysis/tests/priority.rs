@@ -0,0 +1,60 @@
+use poker_analysis::{
+    cost::Cost, draw::DrawTable, policy::Optimal,
+    priority::{PriorityList, Rule, RuleErrorKind, Shape},
+};
+use poker_base::{parse_hand, Paytable, Value, Variant};
+
+#[test]
+fn rules_parse_and_display() {
+    let rule: Rule = "3 to a Straight Flush, 1 gap, no flush penalty".parse().unwrap();
+
+    assert_eq!(rule.shape, Shape::StraightFlushDraw);
+    assert_eq!((rule.cards, rule.gaps, rule.flush_penalty, rule.straight_penalty), (Some(3), Some(1), Some(false), None));
+    assert_eq!(rule.to_string(), "3 to a straight flush, 1 gap, no flush penalty");
+
+    let rule: Rule = "KQJT suited".parse().unwrap();
+    assert_eq!(rule.shape, Shape::Values { values: vec![Value::King, Value::Queen, Value::Jack, Value::Ten], suited: Some(true) });
+
+    for rule in ["High pair", "4 to an outside straight", "2 high cards unsuited", "Discard everything"] {
+        assert_eq!(rule.parse::<Rule>().unwrap().to_string(), rule);
+    }
+
+    let error = "High pair\n\n# comment\n5 to a banana".parse::<PriorityList>().unwrap_err();
+    assert_eq!((error.line, &error.kind), (4, &RuleErrorKind::UnknownHold("5 to a banana".to_string())));
+    assert_eq!(error.to_string(), "invalid rule `5 to a banana` on line 4: unknown hold `5 to a banana`");
+}
+
+/// Errors name the offending word or qualifier.
+#[test]
+fn rule_errors_name_their_cause() {
+    let error = |rule: &str| rule.parse::<Rule>().unwrap_err();
+
+    assert_eq!(error("x to a flush"), RuleErrorKind::InvalidAmount("x".to_string()));
+    assert_eq!(error("3 to a straight flush, two gaps"), RuleErrorKind::InvalidAmount("two".to_string()));
+    assert_eq!(error("High pair, no kicker"), RuleErrorKind::UnknownQualifier("no kicker".to_string()));
+    assert_eq!(error("KQ sorta suited"), RuleErrorKind::UnknownHold("kq sorta suited".to_string()));
+    assert_eq!(error(""), RuleErrorKind::UnknownHold(String::new()));
+}
+
+#[test]
+fn first_matching_rule_decides() {
+    let list: PriorityList = "High pair\n4 to a flush\nKQ unsuited\n1 high card".parse().unwrap();
+
+    assert_eq!(list.choose(&parse_hand("Kh Kd 7h 4h 2h").unwrap(), |_| false), Some((0, vec![0, 1])));
+    assert_eq!(list.choose(&parse_hand("Kh Qd 7h 4h 2h").unwrap(), |_| false), Some((1, vec![0, 2, 3, 4])));
+    assert_eq!(list.choose(&parse_hand("Kh Qd 7c 4h 2s").unwrap(), |_| false), Some((2, vec![0, 1])));
+    assert_eq!(list.choose(&parse_hand("9h 8d 7c 4h 2s").unwrap(), |_| false), None);
+}
+
+/// The simple Jacks or Better strategy shipped with the tool gives up less than a tenth of a percent.
+#[test]
+fn simple_jacks_or_better_strategy_cost() {
+    let list: PriorityList = include_str!("../charts/jacks-or-better-9-6.txt").parse().unwrap();
+    let table = DrawTable::new(Variant::JacksOrBetter, &Paytable::jacks_or_better_9_6());
+
+    let cost = Cost::measure(&table, &list.policy(|_| false), &Optimal(&table));
+
+    assert!((cost.optimal_return - 0.995439).abs() < 1e-6, "return was {}", cost.optimal_return);
+    assert!((cost.strategy_return - 0.994590).abs() < 1e-6, "return was {}", cost.strategy_return);
+    assert!(cost.deviations.windows(2).all(|pair| pair[0].cost() >= pair[1].cost()));
+}
@@ -0,0 +1,28 @@
+use poker_analysis::{draw::DrawTable, progressive::{self, Progressive}, report::Report};
+use poker_base::{Paytable, Variant};
+
+/// Full-pay Jacks or Better breaks even with a royal flush paying 976 per coin, and the return at a
+/// higher payout agrees with solving the game for the raised paytable.
+#[test]
+fn progressive_breakeven() {
+    let paytable = Paytable::jacks_or_better_9_6();
+    let table = DrawTable::new(Variant::JacksOrBetter, &paytable);
+
+    let progressive = Progressive::analyze(&table, 2000f64).unwrap();
+
+    assert!((progressive.expected_return(800f64) - 0.995439).abs() < 1e-6);
+
+    let breakeven = progressive.breakeven().unwrap();
+    assert!((breakeven - 976f64).abs() < 0.5, "breakeven was {breakeven}");
+    assert!((progressive.expected_return(breakeven) - 1f64).abs() < 1e-9);
+
+    // re-optimising is worth more the higher the jackpot climbs
+    assert!(progressive.fixed_strategy_return(1500f64) < progressive.expected_return(1500f64));
+    assert!(progressive.changed_frequency(1500f64) > progressive.changed_frequency(1000f64));
+    assert!(progressive.changes.windows(2).all(|changes| changes[0].royal <= changes[1].royal));
+
+    let raised = DrawTable::new(Variant::JacksOrBetter, &progressive::with_royal(&paytable, 1500).unwrap());
+    let report = Report::solve(&raised);
+
+    assert!((progressive.expected_return(1500f64) - report.expected_return).abs() < 1e-9);
+}
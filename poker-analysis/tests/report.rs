@@ -0,0 +1,40 @@
+use poker_analysis::{draw::DrawTable, report::Report};
+use poker_base::{Paytable, Variant};
+
+/// Published figures for full-pay Jacks or Better: 99.5439% return with a variance of 19.5147.
+#[test]
+fn jacks_or_better_9_6_matches_published_return() {
+    let table = DrawTable::new(Variant::JacksOrBetter, &Paytable::jacks_or_better_9_6());
+    let report = Report::solve(&table);
+
+    assert!((report.coverage - 1f64).abs() < 1e-9);
+    assert!((report.expected_return - 0.995439).abs() < 1e-6, "return was {}", report.expected_return);
+    assert!((report.variance - 19.5147).abs() < 1e-3, "variance was {}", report.variance);
+}
+
+/// Published figures for full-pay Deuces Wild: 100.7620% return.
+#[test]
+fn deuces_wild_full_pay_matches_published_return() {
+    let table = DrawTable::new(Variant::DeucesWild, &Paytable::deuces_wild_full_pay());
+    let report = Report::solve(&table);
+
+    assert!((report.expected_return - 1.007620).abs() < 1e-6, "return was {}", report.expected_return);
+}
+
+/// Published figures for Joker Poker (kings or better) with 20/7/5 payouts: 100.6463% return.
+#[test]
+fn joker_poker_kings_matches_published_return() {
+    let table = DrawTable::new(Variant::JokerPoker, &Paytable::joker_poker_kings());
+    let report = Report::solve(&table);
+
+    assert!((report.expected_return - 1.006463).abs() < 1e-6, "return was {}", report.expected_return);
+}
+
+/// Published figures for 9/6 Double Double Bonus Poker: 98.9808% return, exercising the kicker-specific quad payouts.
+#[test]
+fn double_double_bonus_9_6_matches_published_return() {
+    let table = DrawTable::new(Variant::JacksOrBetter, &Paytable::double_double_bonus_9_6());
+    let report = Report::solve(&table);
+
+    assert!((report.expected_return - 0.989808).abs() < 1e-6, "return was {}", report.expected_return);
+}
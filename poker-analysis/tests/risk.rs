@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+
+use poker_analysis::{draw::DrawTable, report::Report, risk::{self, PayoutDistribution, RiskReport}};
+use poker_base::{Paytable, Variant};
+
+/// Doubling the coin with a chance of 60%.
+fn coin_flip() -> PayoutDistribution {
+    PayoutDistribution { probabilities: BTreeMap::from([(0, 0.4), (2, 0.6)]) }
+}
+
+/// A biased coin flip follows the gambler's ruin: the bankroll is lost with (0.4 / 0.6)^bankroll in the long run.
+#[test]
+fn coin_flip_follows_gamblers_ruin() {
+    let distribution = coin_flip();
+
+    assert!((distribution.mean() - 1.2).abs() < 1e-12);
+    assert!((distribution.variance() - 0.96).abs() < 1e-12);
+    assert!((distribution.n0() - 24f64).abs() < 1e-9);
+
+    assert_eq!(distribution.risk_of_ruin(2, 1), 0f64);
+    // an empty bankroll is ruined before any game, however short or long the session.
+    for games in [0, 1, 2_000, 1_000_000] {
+        assert_eq!(distribution.risk_of_ruin(0, games), 1f64);
+    }
+    assert!((distribution.risk_of_ruin(1, 1) - 0.4).abs() < 1e-12);
+    // lost at once, or won once and lost twice
+    assert!((distribution.risk_of_ruin(1, 3) - (0.4 + 0.6 * 0.4 * 0.4)).abs() < 1e-12);
+
+    for bankroll in [1, 5, 20] {
+        let expected = (0.4f64 / 0.6).powi(bankroll as i32);
+
+        assert!((distribution.long_run_risk_of_ruin(bankroll) - expected).abs() < 1e-9);
+        assert!((distribution.risk_of_ruin(bankroll, 2_000) - expected).abs() < 1e-9);
+    }
+}
+
+/// Full-pay Deuces Wild has a variance of 25.83 on its 0.762% edge, which takes about 445,000 games to dominate
+/// the fluctuation. Its long-run risk of ruin is somewhat below the diffusion estimate exp(-2 * edge * bankroll / variance),
+/// which ignores the skew of the payouts.
+#[test]
+fn deuces_wild_full_pay_risk() {
+    let table = DrawTable::new(Variant::DeucesWild, &Paytable::deuces_wild_full_pay());
+    let distribution = PayoutDistribution::from_report(&Report::solve(&table));
+
+    let report = RiskReport::new(&distribution, 3_000, 1_000);
+
+    assert!((report.expected_return - 1.007620).abs() < 1e-6, "return was {}", report.expected_return);
+    assert!((report.standard_deviation.powi(2) - 25.83).abs() < 1e-2, "variance was {}", report.standard_deviation.powi(2));
+    assert!((report.n0 - 445_000f64).abs() < 1_000f64, "N0 was {}", report.n0);
+    // 1,000 games cannot lose 3,000 coins
+    assert_eq!(report.risk_of_ruin, 0f64);
+
+    let diffusion = (-2f64 * 0.007620 * 3_000f64 / 25.83).exp();
+
+    let ratio = report.long_run_risk_of_ruin / diffusion;
+    assert!(ratio > 0.75 && ratio < 1f64, "long-run risk was {}", report.long_run_risk_of_ruin);
+
+    // the risk grows with the session towards the long-run risk, also past the exactly computed sessions.
+    let risks: Vec<f64> = [2_000, risk::MAX_EXACT_GAMES, 1_000_000, 100_000_000].into_iter().map(|games| distribution.risk_of_ruin(200, games)).collect();
+
+    assert!(risks.windows(2).all(|risks| risks[0] < risks[1]), "risks were {risks:?}");
+    assert!(risks[3] < distribution.long_run_risk_of_ruin(200) * 1.1);
+}
+
+/// Jacks or Better loses in the long run, so every bankroll is lost eventually.
+#[test]
+fn negative_games_are_ruined_in_the_long_run() {
+    let table = DrawTable::new(Variant::JacksOrBetter, &Paytable::jacks_or_better_9_6());
+    let distribution = PayoutDistribution::from_report(&Report::solve(&table));
+
+    assert_eq!(distribution.long_run_risk_of_ruin(10_000), 1f64);
+    assert!(distribution.risk_of_ruin(100, 10_000_000) > 0.99);
+}
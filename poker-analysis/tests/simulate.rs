@@ -0,0 +1,40 @@
+use poker_analysis::{draw::DrawTable, policy::Optimal, simulate::{self, Settings}};
+use poker_base::{Paytable, Variant};
+
+/// Simulated optimal play of full-pay Jacks or Better agrees with its analytical return of 99.5439%
+/// and is reproducible from the seed.
+#[test]
+fn simulation_matches_analytical_return() {
+    let paytable = Paytable::jacks_or_better_9_6();
+    let table = DrawTable::new(Variant::JacksOrBetter, &paytable);
+    let settings = Settings { hands: 200_000, lines: 1, seed: 42, points: 10 };
+
+    let simulation = simulate::simulate(&Variant::JacksOrBetter, &paytable, &Optimal(&table), settings);
+
+    assert_eq!(simulation.hands, 200_000);
+    assert_eq!(simulation.counts.iter().sum::<u64>(), 200_000);
+    assert_eq!(simulation.trajectory.len(), 10);
+    assert_eq!(simulation.trajectory.last().unwrap().bankroll, simulation.payout as i64 - 200_000);
+    assert!(simulation.deviation(0.995439).abs() < 4f64, "return was {}", simulation.realised_return());
+
+    assert_eq!(simulate::simulate(&Variant::JacksOrBetter, &paytable, &Optimal(&table), settings), simulation);
+}
+
+/// Ten-play deals every hand to ten lines: the return stays the same, but the correlated lines of a deal leave
+/// a deviation of 1.9290 per coin, above the 1.4 of ten independent hands.
+#[test]
+fn multi_play_simulation() {
+    let paytable = Paytable::jacks_or_better_9_6();
+    let table = DrawTable::new(Variant::JacksOrBetter, &paytable);
+    let settings = Settings { hands: 50_000, lines: 10, seed: 7, points: 5 };
+
+    let simulation = simulate::simulate(&Variant::JacksOrBetter, &paytable, &Optimal(&table), settings);
+
+    assert_eq!((simulation.hands, simulation.played()), (50_000, 500_000));
+    assert_eq!(simulation.counts.iter().sum::<u64>(), 500_000);
+    assert_eq!(simulation.trajectory.last().unwrap().bankroll, simulation.payout as i64 - 500_000);
+    assert!(simulation.deviation(0.995439).abs() < 4f64, "return was {}", simulation.realised_return());
+
+    let deviation = simulation.variance().sqrt();
+    assert!(deviation > 1.45 && deviation < 2.4, "deviation was {deviation}");
+}
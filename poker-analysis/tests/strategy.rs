@@ -0,0 +1,19 @@
+use std::collections::BTreeMap;
+
+use poker_analysis::strategy::Strategy;
+use poker_base::{parse_hand, ComputedMove};
+
+/// A move computed for one hand serves all of its suit-isomorphic hands, keeping the same cards.
+#[test]
+fn lookup_translates_hold_to_isomorphic_hand() {
+    let computed = ComputedMove { pattern: parse_hand("Ah Kh Qh Jh 2c").unwrap(), keep: vec![0, 1, 2, 3], average_score: 18.5, distribution: BTreeMap::new() };
+    let strategy = Strategy::new([computed]);
+
+    let r#move = strategy.lookup(&parse_hand("2d Js Qs As Ks").unwrap()).unwrap();
+
+    assert_eq!(r#move.pattern, parse_hand("2d Js Qs As Ks").unwrap());
+    assert_eq!(r#move.keep, vec![1, 2, 3, 4]);
+    assert_eq!(r#move.average_score, 18.5);
+
+    assert!(strategy.lookup(&parse_hand("Ah Kh Qh Jh 2h").unwrap()).is_none());
+}
@@ -0,0 +1,22 @@
+use poker_analysis::{cost::Cost, draw::DrawTable, policy::Optimal, priority::PriorityList, synthesis};
+use poker_base::{Paytable, Variant};
+
+/// The synthesised list fits the maximum length, reads back unchanged and plays as well as the search estimated.
+#[test]
+fn synthesised_list_matches_its_measured_return() {
+    let table = DrawTable::new(Variant::JacksOrBetter, &Paytable::jacks_or_better_9_6());
+
+    // not even the line discarding everything fits.
+    assert!(synthesis::synthesize(&table, &Optimal(&table), 0).is_err());
+
+    let synthesis = synthesis::synthesize(&table, &Optimal(&table), 30).unwrap();
+
+    assert!(synthesis.list.rules.len() <= 30);
+    assert_eq!(synthesis.list.to_string().parse::<PriorityList>().unwrap(), synthesis.list);
+
+    let cost = Cost::measure(&table, &synthesis.list.policy(|card| table.is_wild(card)), &Optimal(&table));
+
+    assert!((cost.optimal_return - synthesis.optimal_return).abs() < 1e-9);
+    assert!((cost.strategy_return - synthesis.list_return).abs() < 1e-9, "measured {}, estimated {}", cost.strategy_return, synthesis.list_return);
+    assert!(cost.lost() < 0.002, "lost {}", cost.lost());
+}
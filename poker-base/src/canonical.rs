@@ -0,0 +1,95 @@
+//! Suit isomorphism: cards that only differ by a permutation of the suits play identically.
+//! Jokers have no suit and are left untouched by the permutations.
+
+use crate::{Card, Suit};
+
+/// All 24 permutations of the suits.
+const PERMUTATIONS: [[Suit; 4]; 24] = permutations();
+
+const fn permutations() -> [[Suit; 4]; 24] {
+    let mut permutations = [Suit::ALL; 24];
+    let mut index = 0;
+
+    let mut a = 0;
+    while a < 4 {
+        let mut b = 0;
+        while b < 4 {
+            let mut c = 0;
+            while c < 4 {
+                if a != b && a != c && b != c {
+                    permutations[index] = [Suit::ALL[a], Suit::ALL[b], Suit::ALL[c], Suit::ALL[6 - a - b - c]];
+                    index += 1;
+                }
+
+                c += 1;
+            }
+
+            b += 1;
+        }
+
+        a += 1;
+    }
+
+    permutations
+}
+
+/// Applies the suit permutation to the cards and returns their sorted keys (ordered by value, then suit).
+fn permute<const N: usize>(cards: &[Card; N], permutation: &[Suit; 4]) -> [u8; N] {
+    let mut keys = cards.map(|card| key(apply(card, permutation)));
+    keys.sort_unstable();
+
+    keys
+}
+
+fn apply(card: Card, permutation: &[Suit; 4]) -> Card {
+    match card.is_joker() {
+        true => card,
+        false => Card { suit: permutation[card.suit.index()], ..card },
+    }
+}
+
+fn key(card: Card) -> u8 {
+    (card.value as u8) * 4 + card.suit.index() as u8
+}
+
+/// Returns the canonical representative of all hands the given hand is suit-isomorphic to.
+/// The cards of the representative are sorted by value.
+pub fn canonicalize<const N: usize>(cards: &[Card; N]) -> [Card; N] {
+    let (permutation, _) = PERMUTATIONS
+        .iter()
+        .map(|permutation| (permutation, permute(cards, permutation)))
+        .min_by_key(|(_, keys)| *keys)
+        .unwrap();
+
+    let mut canonical = cards.map(|card| apply(card, permutation));
+    canonical.sort_unstable_by_key(|&card| key(card));
+
+    canonical
+}
+
+/// The amount of distinct hands that are suit-isomorphic to the given hand (including itself).
+pub fn suit_multiplicity<const N: usize>(cards: &[Card; N]) -> usize {
+    let mut hands: Vec<[u8; N]> = PERMUTATIONS
+        .iter()
+        .map(|permutation| permute(cards, permutation))
+        .collect();
+
+    hands.sort_unstable();
+    hands.dedup();
+
+    hands.len()
+}
+
+/// Finds a suit permutation (indexed by [Suit::index]) turning the cards of `from` into those of `to`,
+/// disregarding their order, if the hands are suit-isomorphic.
+pub fn isomorphism<const N: usize>(from: &[Card; N], to: &[Card; N]) -> Option<[Suit; 4]> {
+    let mut target = to.map(key);
+    target.sort_unstable();
+
+    PERMUTATIONS.iter().find(|permutation| permute(from, permutation) == target).copied()
+}
+
+/// Applies a suit permutation (as returned by [isomorphism]) to the card.
+pub fn permute_card(card: Card, permutation: &[Suit; 4]) -> Card {
+    apply(card, permutation)
+}
@@ -0,0 +1,270 @@
+//! Equity of Texas Hold'em hands against each other: the chance of winning, tying and losing at showdown.
+
+use core::fmt;
+use std::error::Error;
+
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{strength::{best_strength, Strength}, Card};
+
+/// The amount of community cards at showdown.
+pub const BOARD_SIZE: usize = 5;
+/// The largest amount of board completions that is enumerated exactly by [Method::Auto].
+pub const EXACT_LIMIT: u64 = 2_000_000;
+/// The amount of boards dealt by [Method::Auto] when enumeration is infeasible.
+pub const DEFAULT_TRIALS: u64 = 500_000;
+
+/// How the boards are generated.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Method {
+    /// Enumerates every possible completion of the board.
+    Exact,
+    /// Deals the given amount of random completions of the board, reproducibly from the given seed.
+    MonteCarlo { trials: u64, seed: u64 },
+    /// Exact enumeration if at most [EXACT_LIMIT] completions exist, Monte Carlo with [DEFAULT_TRIALS] otherwise.
+    Auto,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum EquityError {
+    /// At least two hands are required.
+    TooFewHands,
+    /// More than [BOARD_SIZE] board cards were given.
+    BoardSize(usize),
+    /// A card was given more than once (in hands, board or dead cards).
+    DuplicateCard(Card),
+    /// Jokers have no place in Hold'em.
+    Joker,
+    /// There are not enough cards left to complete the board.
+    NotEnoughCards,
+    /// No combination of the range (of the given index) is possible with the known cards.
+    EmptyRange(usize),
+    /// The ranges cannot be dealt without sharing cards.
+    ConflictingRanges,
+}
+
+impl fmt::Display for EquityError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EquityError::TooFewHands => write!(formatter, "At least two hands are required"),
+            EquityError::BoardSize(size) => write!(formatter, "A board consists of at most {BOARD_SIZE} cards, not {size}"),
+            EquityError::DuplicateCard(card) => write!(formatter, "Card {card} was given more than once"),
+            EquityError::Joker => write!(formatter, "Jokers cannot be played"),
+            EquityError::NotEnoughCards => write!(formatter, "Not enough cards left to complete the board"),
+            EquityError::EmptyRange(index) => write!(formatter, "Range {} has no possible combination", index + 1),
+            EquityError::ConflictingRanges => write!(formatter, "The ranges cannot be dealt without sharing cards"),
+        }
+    }
+}
+
+impl Error for EquityError {}
+
+/// The showdown results of one hand.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct HandEquity {
+    /// The amount of boards the hand wins alone.
+    pub wins: u64,
+    /// The amount of boards the hand ties for the best hand.
+    pub ties: u64,
+    /// The sum of the share of the pot won on each board.
+    pub share: f64,
+}
+
+/// The equity of each hand over the evaluated boards.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Equity {
+    /// In the order the hands were given.
+    pub hands: Vec<HandEquity>,
+    /// The amount of boards evaluated.
+    pub boards: u64,
+    /// Whether all possible boards were enumerated.
+    pub exact: bool,
+}
+
+impl Equity {
+    /// The probability of the hand winning alone.
+    pub fn win(&self, hand: usize) -> f64 {
+        self.hands[hand].wins as f64 / self.boards as f64
+    }
+
+    /// The probability of the hand tying for the best hand.
+    pub fn tie(&self, hand: usize) -> f64 {
+        self.hands[hand].ties as f64 / self.boards as f64
+    }
+
+    pub fn lose(&self, hand: usize) -> f64 {
+        1f64 - self.win(hand) - self.tie(hand)
+    }
+
+    /// The expected share of the pot, ties splitting it equally.
+    pub fn equity(&self, hand: usize) -> f64 {
+        self.hands[hand].share / self.boards as f64
+    }
+}
+
+impl fmt::Display for Equity {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "{} boards ({})", self.boards, if self.exact { "exact" } else { "Monte Carlo" })?;
+        writeln!(formatter, "{:<6} {:>9} {:>9} {:>9} {:>9}", "Hand", "Win", "Tie", "Lose", "Equity")?;
+
+        for hand in 0..self.hands.len() {
+            writeln!(
+                formatter, "{:<6} {:>8.4}% {:>8.4}% {:>8.4}% {:>8.4}%",
+                hand + 1, self.win(hand) * 100f64, self.tie(hand) * 100f64, self.lose(hand) * 100f64, self.equity(hand) * 100f64
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The amount of ways to choose `k` of `n` items.
+pub(crate) fn binomial(n: usize, k: usize) -> u64 {
+    (0..k).fold(1u64, |result, i| result * (n - i) as u64 / (i + 1) as u64)
+}
+
+/// The cards not in use, after checking that no card is given twice.
+pub(crate) fn remaining(hands: &[&[Card]], board: &[Card], dead: &[Card]) -> Result<Vec<Card>, EquityError> {
+    let mut used: Vec<Card> = Vec::new();
+
+    for &card in hands.iter().flat_map(|hand| hand.iter()).chain(board).chain(dead) {
+        if card.is_joker() {
+            return Err(EquityError::Joker);
+        }
+
+        if used.contains(&card) {
+            return Err(EquityError::DuplicateCard(card));
+        }
+
+        used.push(card);
+    }
+
+    Ok(Card::full_deck().into_iter().filter(|card| !used.contains(card)).collect())
+}
+
+/// The share of the pot each hand wins, the best hands splitting it equally. Hands of `None` do not compete.
+pub(crate) fn pot_shares<T: Ord>(hands: &[Option<T>]) -> Vec<f64> {
+    let best = hands.iter().max().and_then(Option::as_ref);
+    let winners = hands.iter().filter(|hand| hand.is_some() && hand.as_ref() == best).count();
+
+    hands
+        .iter()
+        .map(|hand| match hand.is_some() && hand.as_ref() == best {
+            true => 1f64 / winners as f64,
+            false => 0f64,
+        })
+        .collect()
+}
+
+impl HandEquity {
+    /// Records the share of the pot won on one board.
+    pub(crate) fn record(&mut self, share: f64) {
+        if share == 1f64 {
+            self.wins += 1;
+        } else if share != 0f64 {
+            self.ties += 1;
+        }
+
+        self.share += share;
+    }
+}
+
+/// Settles the showdown of one board between hands of the given strengths.
+pub(crate) fn showdown(results: &mut [HandEquity], strengths: &[Strength]) {
+    let strengths: Vec<Option<Strength>> = strengths.iter().copied().map(Some).collect();
+
+    for (result, share) in results.iter_mut().zip(pot_shares(&strengths)) {
+        result.record(share);
+    }
+}
+
+/// Calls `on_board` with every board completing the given one (or with random completions for Monte Carlo)
+/// and returns the amount of boards dealt and whether they were enumerated exactly.
+pub(crate) fn deal_boards(
+    hands: &[&[Card]], board: &[Card], dead: &[Card], method: Method,
+    mut on_board: impl FnMut(&[Card; BOARD_SIZE]),
+) -> Result<(u64, bool), EquityError> {
+    if hands.len() < 2 {
+        return Err(EquityError::TooFewHands);
+    }
+
+    if board.len() > BOARD_SIZE {
+        return Err(EquityError::BoardSize(board.len()));
+    }
+
+    let mut deck = remaining(hands, board, dead)?;
+    let missing = BOARD_SIZE - board.len();
+
+    if deck.len() < missing {
+        return Err(EquityError::NotEnoughCards);
+    }
+
+    let combinations = binomial(deck.len(), missing);
+
+    let method = match method {
+        Method::Auto if combinations <= EXACT_LIMIT => Method::Exact,
+        Method::Auto => Method::MonteCarlo { trials: DEFAULT_TRIALS, seed: 0 },
+        method => method,
+    };
+
+    let mut full = [Card::JOKER; BOARD_SIZE];
+    full[..board.len()].copy_from_slice(board);
+
+    match method {
+        Method::MonteCarlo { trials, seed } => {
+            let mut rng = StdRng::seed_from_u64(seed);
+
+            for _ in 0..trials {
+                let (dealt, _) = deck.partial_shuffle(&mut rng, missing);
+                full[board.len()..].copy_from_slice(dealt);
+
+                on_board(&full);
+            }
+
+            Ok((trials, false))
+        },
+        _ => {
+            for dealt in deck.iter().copied().combinations(missing) {
+                full[board.len()..].copy_from_slice(&dealt);
+
+                on_board(&full);
+            }
+
+            Ok((combinations, true))
+        },
+    }
+}
+
+/// Computes the equity of the hands on every board completing the given one, each hand being evaluated by
+/// `evaluate(hand, board)`. Shared by the games built on community cards.
+pub(crate) fn calculate_with(
+    hands: &[&[Card]], board: &[Card], dead: &[Card], method: Method,
+    evaluate: impl Fn(&[Card], &[Card; BOARD_SIZE]) -> Strength,
+) -> Result<Equity, EquityError> {
+    let mut results = vec![HandEquity::default(); hands.len()];
+    let mut strengths = Vec::with_capacity(hands.len());
+
+    let (boards, exact) = deal_boards(hands, board, dead, method, |full| {
+        strengths.clear();
+        strengths.extend(hands.iter().map(|hand| evaluate(hand, full)));
+
+        showdown(&mut results, &strengths);
+    })?;
+
+    Ok(Equity { hands: results, boards, exact })
+}
+
+/// Computes the Hold'em equity of two or more hole card hands, given the (partial) board and dead cards.
+pub fn calculate(hands: &[[Card; 2]], board: &[Card], dead: &[Card], method: Method) -> Result<Equity, EquityError> {
+    let hands: Vec<&[Card]> = hands.iter().map(|hand| &hand[..]).collect();
+
+    calculate_with(&hands, board, dead, method, holdem_strength)
+}
+
+/// The strength of the best Hold'em hand of the hole cards on the complete board.
+pub(crate) fn holdem_strength(hand: &[Card], board: &[Card; BOARD_SIZE]) -> Strength {
+    let [a, b, c, d, e] = *board;
+
+    best_strength(&[hand[0], hand[1], a, b, c, d, e])
+}
@@ -0,0 +1,156 @@
+//! The rules of a video poker game: its deck, hand evaluation and paytables, and the resolution of games named on the command line.
+//! Adding a game requires implementing [GameVariant] and adding an arm to [Variant](crate::Variant), which
+//! names the game in computation blocks and states. Computation blocks carry five card patterns, so the farm
+//! rejects games dealing another [GameVariant::hand_size].
+
+use std::{error::Error, fs};
+
+use crate::{compute_rank, compute_wild_rank, Card, Deck, Evaluation, Paytable, Value, Variant};
+
+/// The rules of a video poker game.
+pub trait GameVariant: Send + Sync {
+    /// The name the game is known by, e.g. on the command line.
+    fn name(&self) -> &'static str;
+
+    /// The deck the game is dealt from.
+    fn deck(&self) -> Vec<Card>;
+
+    /// The amount of cards dealt.
+    fn hand_size(&self) -> usize {
+        5
+    }
+
+    /// Whether the card is wild, standing in for any card.
+    fn is_wild(&self, _card: Card) -> bool {
+        false
+    }
+
+    /// Evaluates the given final hand of [GameVariant::hand_size] cards.
+    fn evaluate(&self, cards: Vec<Card>) -> Evaluation;
+
+    /// The paytable the game is computed with by default.
+    fn paytable(&self) -> Paytable;
+
+    /// The built-in paytables of the game.
+    fn paytables(&self) -> Vec<Paytable> {
+        vec![self.paytable()]
+    }
+}
+
+/// Jacks or Better and its relatives without wild cards (e.g. Bonus Poker).
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Default)]
+pub struct JacksOrBetter;
+
+impl GameVariant for JacksOrBetter {
+    fn name(&self) -> &'static str {
+        "jacks-or-better"
+    }
+
+    fn deck(&self) -> Vec<Card> {
+        Deck::standard().cards.to_vec()
+    }
+
+    fn evaluate(&self, cards: Vec<Card>) -> Evaluation {
+        compute_rank(cards).into()
+    }
+
+    fn paytable(&self) -> Paytable {
+        Paytable::win2day()
+    }
+
+    fn paytables(&self) -> Vec<Paytable> {
+        vec![
+            Paytable::win2day(),
+            Paytable::jacks_or_better_9_6(),
+            Paytable::bonus_poker_8_5(),
+            Paytable::double_bonus_10_7(),
+            Paytable::double_double_bonus_9_6(),
+        ]
+    }
+}
+
+/// All deuces are wild.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Default)]
+pub struct DeucesWild;
+
+impl GameVariant for DeucesWild {
+    fn name(&self) -> &'static str {
+        "deuces-wild"
+    }
+
+    fn deck(&self) -> Vec<Card> {
+        Deck::standard().cards.to_vec()
+    }
+
+    fn is_wild(&self, card: Card) -> bool {
+        card.value == Value::Two
+    }
+
+    fn evaluate(&self, cards: Vec<Card>) -> Evaluation {
+        compute_wild_rank(cards, |card| self.is_wild(card))
+    }
+
+    fn paytable(&self) -> Paytable {
+        Paytable::deuces_wild_full_pay()
+    }
+}
+
+/// Played with a 53-card deck, the joker being wild.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Default)]
+pub struct JokerPoker;
+
+impl GameVariant for JokerPoker {
+    fn name(&self) -> &'static str {
+        "joker-poker"
+    }
+
+    fn deck(&self) -> Vec<Card> {
+        Deck::with_joker().cards.to_vec()
+    }
+
+    fn is_wild(&self, card: Card) -> bool {
+        card.is_joker()
+    }
+
+    fn evaluate(&self, cards: Vec<Card>) -> Evaluation {
+        compute_wild_rank(cards, |card| self.is_wild(card))
+    }
+
+    fn paytable(&self) -> Paytable {
+        Paytable::joker_poker_kings()
+    }
+}
+
+/// Resolves a paytable by the name of a preset or the path to a JSON file.
+pub fn paytable(paytable: &str) -> Result<Paytable, Box<dyn Error>> {
+    match Paytable::preset(paytable) {
+        Some(paytable) => Ok(paytable),
+        None => Ok(serde_json::from_str(&fs::read_to_string(paytable)?)?),
+    }
+}
+
+/// Resolves a game named on the command line by the name of a variant (using its default paytable), the
+/// name of a paytable preset or `<variant>:<paytable>`, where the paytable may also be a JSON file.
+/// Built-in paytables of another variant are rejected.
+pub fn resolve(game: &str) -> Result<(Variant, Paytable), Box<dyn Error>> {
+    if let Some((variant, name)) = game.split_once(':') {
+        let (variant, paytable): (Variant, _) = (variant.parse()?, paytable(name)?);
+
+        if let Some(other) = Variant::ALL.into_iter().find(|other| other.paytables().contains(&paytable) && !variant.paytables().contains(&paytable)) {
+            return Err(format!("The paytable `{}` is made for `{other}`, not `{variant}`.", paytable.name).into());
+        }
+
+        return Ok((variant, paytable));
+    }
+
+    if let Ok(variant) = game.parse::<Variant>() {
+        return Ok((variant, variant.paytable()));
+    }
+
+    let variant = Variant::ALL
+        .into_iter()
+        .find(|variant| variant.paytables().iter().any(|paytable| paytable.name == game))
+        .unwrap_or_default();
+
+    Ok((variant, paytable(game)?))
+}
@@ -1,7 +1,27 @@
-use std::{cmp::Ordering, hash::Hash};
+use std::{cmp::Ordering, collections::BTreeMap, fmt, hash::Hash};
 
 use serde::{Serialize, Deserialize};
 
+pub mod canonical;
+pub mod equity;
+pub mod game;
+pub mod parse;
+pub mod lowball;
+pub mod omaha;
+pub mod paytable;
+pub mod range;
+pub mod strength;
+pub mod variant;
+pub mod wild;
+
+pub use game::GameVariant;
+pub use lowball::{compute_ace_to_five_rank, compute_deuce_to_seven_rank, Lowball, LowRank};
+pub use parse::{parse_cards, parse_deck_cards, parse_deck_hand, parse_hand, ParseError, ParseErrorKind};
+pub use paytable::{Category, Paytable, Payout, ValueSet};
+pub use strength::{best_strength, HandCategory, Strength};
+pub use variant::Variant;
+pub use wild::compute_wild_rank;
+
 /// The suit of a card.
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Serialize, Deserialize)]
 pub enum Suit {
@@ -15,6 +35,21 @@ pub enum Suit {
     Diamond,
 }
 
+impl Suit {
+    /// All suits, in deck order.
+    pub const ALL: [Suit; 4] = [Suit::Heart, Suit::Spade, Suit::Club, Suit::Diamond];
+
+    /// The position of the suit in [Suit::ALL].
+    pub const fn index(self) -> usize {
+        match self {
+            Suit::Heart => 0,
+            Suit::Spade => 1,
+            Suit::Club => 2,
+            Suit::Diamond => 3,
+        }
+    }
+}
+
 impl PartialOrd for Suit {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -31,15 +66,28 @@ impl TryFrom<char> for Suit {
     type Error = char;
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '♥' | 'H' => Ok(Suit::Heart),
-            '♠' | 'S' => Ok(Suit::Spade),
-            '♣' | 'C' => Ok(Suit::Club),
-            '♦' | 'D' => Ok(Suit::Diamond),
+            '♥' | 'H' | 'h' => Ok(Suit::Heart),
+            '♠' | 'S' | 's' => Ok(Suit::Spade),
+            '♣' | 'C' | 'c' => Ok(Suit::Club),
+            '♦' | 'D' | 'd' => Ok(Suit::Diamond),
             _ => Err(value),
         }
     }
 }
 
+impl fmt::Display for Suit {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Suit::Heart => '♥',
+            Suit::Spade => '♠',
+            Suit::Club => '♣',
+            Suit::Diamond => '♦',
+        };
+
+        write!(formatter, "{symbol}")
+    }
+}
+
 /// The value of a card.
 #[derive(PartialOrd, Ord, PartialEq, Eq, Debug, Clone, Copy, Hash, Serialize, Deserialize)]
 pub enum Value {
@@ -71,6 +119,17 @@ pub enum Value {
     King,
     /// A
     Ace,
+    /// The joker, only part of [Deck::with_joker]. It is not part of [Value::ALL].
+    Joker,
+}
+
+impl Value {
+    /// All values, from lowest to highest.
+    pub const ALL: [Value; 13] = [
+        Value::Two, Value::Three, Value::Four, Value::Five, Value::Six, Value::Seven,
+        Value::Eight, Value::Nine, Value::Ten, Value::Jack, Value::Queen, Value::King,
+        Value::Ace
+    ];
 }
 
 impl TryFrom<char> for Value {
@@ -85,16 +144,40 @@ impl TryFrom<char> for Value {
             '7' => Ok(Value::Seven),
             '8' => Ok(Value::Eight),
             '9' => Ok(Value::Nine),
-            'T' => Ok(Value::Ten),
-            'J' => Ok(Value::Jack),
-            'Q' => Ok(Value::Queen),
-            'K' => Ok(Value::King),
-            'A' => Ok(Value::Ace),
+            'T' | 't' => Ok(Value::Ten),
+            'J' | 'j' => Ok(Value::Jack),
+            'Q' | 'q' => Ok(Value::Queen),
+            'K' | 'k' => Ok(Value::King),
+            'A' | 'a' => Ok(Value::Ace),
+            'X' | 'x' | '🃏' => Ok(Value::Joker),
             _ => Err(value),
         }
     }
 }
 
+impl fmt::Display for Value {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol = match self {
+            Value::Two => '2',
+            Value::Three => '3',
+            Value::Four => '4',
+            Value::Five => '5',
+            Value::Six => '6',
+            Value::Seven => '7',
+            Value::Eight => '8',
+            Value::Nine => '9',
+            Value::Ten => 'T',
+            Value::Jack => 'J',
+            Value::Queen => 'Q',
+            Value::King => 'K',
+            Value::Ace => 'A',
+            Value::Joker => 'X',
+        };
+
+        write!(formatter, "{symbol}")
+    }
+}
+
 /// A card in a standard 52-card deck.
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Serialize, Deserialize)]
 pub struct Card {
@@ -103,14 +186,17 @@ pub struct Card {
 }
 
 impl Card {
+    /// The joker. Its suit carries no meaning; any card of value [Value::Joker] is a joker.
+    pub const JOKER: Card = Card { suit: Suit::Spade, value: Value::Joker };
+
+    pub const fn is_joker(&self) -> bool {
+        matches!(self.value, Value::Joker)
+    }
+
     pub fn full_deck() -> Vec<Self> {
         let mut deck = Vec::with_capacity(52);
-        for &suit in &[Suit::Heart, Suit::Spade, Suit::Club, Suit::Diamond] {
-            for &value in &[
-                Value::Two, Value::Three, Value::Four, Value::Five, Value::Six, Value::Seven,
-                Value::Eight, Value::Nine, Value::Ten, Value::Jack, Value::Queen, Value::King,
-                Value::Ace
-            ] {
+        for suit in Suit::ALL {
+            for value in Value::ALL {
                 deck.push(Card { suit, value });
             }
         }
@@ -142,6 +228,16 @@ impl TryFrom<(char, char)> for Card {
     }
 }
 
+impl fmt::Display for Card {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_joker() {
+            return write!(formatter, "{}", self.value);
+        }
+
+        write!(formatter, "{}{}", self.value, self.suit)
+    }
+}
+
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub struct SameKind {
     pub value: Value,
@@ -163,16 +259,56 @@ impl Ord for SameKind {
     }
 }
 
+/// A fixed amount of cards, e.g. a hand.
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct Deck<const N: usize> {
     pub cards: [Card; N],
 }
 
+impl Deck<52> {
+    /// The standard 52-card deck.
+    pub fn standard() -> Self {
+        Self { cards: Card::full_deck().try_into().unwrap() }
+    }
+}
+
+impl Deck<53> {
+    /// The standard deck along with a joker.
+    pub fn with_joker() -> Self {
+        let mut cards = [Card::JOKER; 53];
+        cards[..52].copy_from_slice(&Deck::standard().cards);
+
+        Self { cards }
+    }
+}
+
+impl<const N: usize> Deck<N> {
+    /// The cards of the deck that are not contained in `removed`.
+    pub fn remaining(&self, removed: &[Card]) -> Vec<Card> {
+        self.cards.iter().filter(|card| !removed.contains(card)).copied().collect()
+    }
+}
+
+impl<const N: usize> fmt::Display for Deck<N> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, card) in self.cards.iter().enumerate() {
+            if index != 0 {
+                write!(formatter, " ")?;
+            }
+
+            write!(formatter, "{card}")?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Computes the rank of the given cards.
 /// # Panics
-/// if cards not len of 5.
+/// if cards not len of 5 or a joker is given (see [compute_wild_rank]).
 pub fn compute_rank(mut cards: Vec<Card>) -> Rank {
     assert_eq!(cards.len(), 5, "cards must be of length 5");
+    assert!(cards.iter().all(|card| !card.is_joker()), "jokers cannot be evaluated");
 
     cards.sort();
     cards.reverse();
@@ -212,10 +348,16 @@ pub fn compute_rank(mut cards: Vec<Card>) -> Rank {
         kinds.push(SameKind { value: cards[4].value, amount: current_kind })
     }
 
+    // the wheel (A-5-4-3-2) is a straight with the five as its highest card.
+    if cards.iter().map(|card| card.value).eq([Value::Ace, Value::Five, Value::Four, Value::Three, Value::Two]) {
+        straight = true;
+        cards.rotate_left(1);
+    }
+
     if flush && straight {
         let first = cards[0];
 
-        if first.value == Value::Ten {
+        if first.value == Value::Ace {
             return Rank::RoyalFlush(first.suit);
         } else {
             return Rank::StraightFlush(first.into());
@@ -229,7 +371,9 @@ pub fn compute_rank(mut cards: Vec<Card>) -> Rank {
         let kind = kinds[0];
 
         if kind.amount == 4 {
-            return Rank::FourOfAKind(kind.value);
+            let kicker = cards.iter().find(|card| card.value != kind.value).unwrap().value;
+
+            return Rank::FourOfAKind { value: kind.value, kicker };
         } else if kind.amount == 3 && kinds.len() == 2 {
             let pair = kinds[1];
 
@@ -298,13 +442,60 @@ pub enum Rank {
         three_of_a_kind: Value,
         pair: Value
     },
-    FourOfAKind(Value),
+    FourOfAKind {
+        value: Value,
+        /// The value of the fifth card.
+        kicker: Value
+    },
     StraightFlush(StraightFlushDetails),
-    RoyalFlush(Suit)
+    RoyalFlush(Suit),
+    /// Only possible with wild cards.
+    FiveOfAKind(Value)
+}
+
+/// The rank of a hand along with the amount of wild cards it was formed with.
+#[derive(PartialOrd, Ord, PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Evaluation {
+    pub rank: Rank,
+    pub wilds: usize,
+}
+
+impl From<Rank> for Evaluation {
+    fn from(rank: Rank) -> Self {
+        Self { rank, wilds: 0 }
+    }
+}
+
+impl fmt::Display for Rank {
+    /// Formats the rank as e.g. `TwoPair(K, 7)`; the notation is understood by its [std::str::FromStr] implementation.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rank::HighCard(value) => write!(formatter, "HighCard({value})"),
+            Rank::Pair(value) => write!(formatter, "Pair({value})"),
+            Rank::TwoPair { a, b } => write!(formatter, "TwoPair({a}, {b})"),
+            Rank::ThreeOfAKind(value) => write!(formatter, "ThreeOfAKind({value})"),
+            Rank::Straight { high, suit } => write!(formatter, "Straight({high}{suit})"),
+            Rank::Flush(value) => write!(formatter, "Flush({value})"),
+            Rank::FullHouse { three_of_a_kind, pair } => write!(formatter, "FullHouse({three_of_a_kind}, {pair})"),
+            Rank::FourOfAKind { value, kicker } => write!(formatter, "FourOfAKind({value}, {kicker})"),
+            Rank::StraightFlush(StraightFlushDetails { high, suit }) => write!(formatter, "StraightFlush({high}{suit})"),
+            Rank::RoyalFlush(suit) => write!(formatter, "RoyalFlush({suit})"),
+            Rank::FiveOfAKind(value) => write!(formatter, "FiveOfAKind({value})"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ComputationBlock {
+    /// The name of the server job the block belongs to.
+    #[serde(default)]
+    pub job: String,
+    /// The game the patterns are to be computed for.
+    #[serde(default)]
+    pub variant: Variant,
+    /// The paytable to compute with, the default paytable of the variant if `None`.
+    #[serde(default)]
+    pub paytable: Option<Paytable>,
     pub patterns: Vec<[Card; 5]>,
 }
 
@@ -315,7 +506,10 @@ pub struct ComputedMove {
     /// The indices of the cards to keep.
     pub keep: Vec<usize>,
     // /// The average score keeping these cards yields.
-    pub average_score: f64
+    pub average_score: f64,
+    /// The distribution of the scores keeping these cards yields (score: amount of draws).
+    #[serde(default)]
+    pub distribution: BTreeMap<usize, u64>
 }
 
 impl PartialEq<ComputedMove> for ComputedMove {
@@ -334,6 +528,9 @@ impl Hash for ComputedMove {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ComputedBlock {
+    /// The name of the server job the block belongs to, copied from the [ComputationBlock].
+    #[serde(default)]
+    pub job: String,
     pub moves: Vec<ComputedMove>,
 }
 
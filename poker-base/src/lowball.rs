@@ -0,0 +1,133 @@
+//! Lowball hand evaluation, where the lowest hand wins.
+//!
+//! In deuce-to-seven (2-7) lowball aces are high and straights and flushes count against the hand,
+//! making 7-5-4-3-2 of mixed suits the best hand. In ace-to-five (A-5) lowball aces are low and
+//! straights and flushes are ignored, making A-2-3-4-5 the best hand.
+
+use core::{cmp::Ordering, fmt};
+
+use crate::{strength::{best_strength, HandCategory}, Card, Value};
+
+/// A lowball ranking, selecting the evaluator of a draw lowball game.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum Lowball {
+    /// Deuce-to-seven, e.g. 2-7 triple draw.
+    DeuceToSeven,
+    /// Ace-to-five, e.g. California lowball.
+    AceToFive,
+}
+
+impl Lowball {
+    /// Evaluates the given five cards.
+    /// # Panics
+    /// if cards not len of 5 or a joker is given.
+    pub fn evaluate(&self, cards: Vec<Card>) -> LowRank {
+        match self {
+            Lowball::DeuceToSeven => compute_deuce_to_seven_rank(cards),
+            Lowball::AceToFive => compute_ace_to_five_rank(cards),
+        }
+    }
+}
+
+/// The rank of a lowball hand. Ordered such that the better (lower) hand is greater.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub struct LowRank {
+    category: HandCategory,
+    /// The values deciding between hands of the same category (aces counting as one in A-5), most significant first.
+    ranks: [u8; 5],
+}
+
+impl LowRank {
+    /// The category of the hand, e.g. [HandCategory::Pair] for a paired hand.
+    pub fn category(&self) -> HandCategory {
+        self.category
+    }
+
+    /// The values deciding between hands of the same category, most significant first.
+    pub fn values(&self) -> [Value; 5] {
+        self.ranks.map(|rank| match rank {
+            1 | 14 => Value::Ace,
+            rank => Value::ALL[rank as usize - 2],
+        })
+    }
+}
+
+impl PartialOrd for LowRank {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LowRank {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.category, other.ranks).cmp(&(self.category, self.ranks))
+    }
+}
+
+impl fmt::Display for LowRank {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{} (", self.category)?;
+
+        for value in self.values() {
+            write!(formatter, "{value}")?;
+        }
+
+        write!(formatter, ")")
+    }
+}
+
+/// Computes the deuce-to-seven lowball rank of the given cards.
+/// # Panics
+/// if cards not len of 5 or a joker is given.
+pub fn compute_deuce_to_seven_rank(cards: Vec<Card>) -> LowRank {
+    assert_eq!(cards.len(), 5, "cards must be of length 5");
+
+    let strength = best_strength(&cards);
+
+    // the ace only plays high, so A-2-3-4-5 is no straight but ace high.
+    let (category, values) = match (strength.category, strength.values[0]) {
+        (HandCategory::Straight, Value::Five) => (HandCategory::HighCard, [Value::Ace, Value::Five, Value::Four, Value::Three, Value::Two]),
+        (HandCategory::StraightFlush, Value::Five) => (HandCategory::Flush, [Value::Ace, Value::Five, Value::Four, Value::Three, Value::Two]),
+        _ => (strength.category, strength.values),
+    };
+
+    LowRank { category, ranks: values.map(|value| value as u8 + 2) }
+}
+
+/// Computes the ace-to-five lowball rank of the given cards.
+/// # Panics
+/// if cards not len of 5 or a joker is given.
+pub fn compute_ace_to_five_rank(cards: Vec<Card>) -> LowRank {
+    assert_eq!(cards.len(), 5, "cards must be of length 5");
+    assert!(cards.iter().all(|card| !card.is_joker()), "jokers cannot be evaluated");
+
+    let mut counts = [0u8; 14];
+    for card in &cards {
+        let rank = match card.value {
+            Value::Ace => 1,
+            value => value as usize + 2,
+        };
+
+        counts[rank - 1] += 1;
+    }
+
+    // the ranks grouped by their amount, the larger groups first, then the higher ranks.
+    let mut groups: Vec<(u8, u8)> = (1..=13u8).filter(|&rank| counts[rank as usize - 1] != 0).map(|rank| (counts[rank as usize - 1], rank)).collect();
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+
+    let category = match (groups[0].0, groups.get(1).map(|group| group.0)) {
+        (4, _) => HandCategory::FourOfAKind,
+        (3, Some(2)) => HandCategory::FullHouse,
+        (3, _) => HandCategory::ThreeOfAKind,
+        (2, Some(2)) => HandCategory::TwoPair,
+        (2, _) => HandCategory::Pair,
+        _ => HandCategory::HighCard,
+    };
+
+    let mut ranks = [1u8; 5];
+    for (index, (_, rank)) in groups.iter().enumerate() {
+        ranks[index] = *rank;
+    }
+
+    LowRank { category, ranks }
+}
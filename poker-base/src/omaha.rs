@@ -0,0 +1,204 @@
+//! Omaha and Omaha Hi/Lo: four hole cards, of which exactly two are played with exactly three board cards.
+
+use core::{cmp::Ordering, fmt};
+
+use itertools::Itertools;
+
+use crate::{
+    equity::{self, EquityError, HandEquity, Method, BOARD_SIZE},
+    strength::{best_strength, Strength},
+    Card, Value,
+};
+
+/// The amount of hole cards.
+pub const HOLE_SIZE: usize = 4;
+
+/// The pairs of hole card indices that can be played.
+const HOLE_PAIRS: [[usize; 2]; 6] = [[0, 1], [0, 2], [0, 3], [1, 2], [1, 3], [2, 3]];
+/// The triples of board card indices that can be played.
+const BOARD_TRIPLES: [[usize; 3]; 10] = [[0, 1, 2], [0, 1, 3], [0, 1, 4], [0, 2, 3], [0, 2, 4], [0, 3, 4], [1, 2, 3], [1, 2, 4], [1, 3, 4], [2, 3, 4]];
+
+/// The 60 hands of exactly two hole cards and three board cards.
+fn hands<'a>(hole: &'a [Card; HOLE_SIZE], board: &'a [Card; BOARD_SIZE]) -> impl Iterator<Item = [Card; 5]> + 'a {
+    HOLE_PAIRS.iter().cartesian_product(BOARD_TRIPLES.iter())
+        .map(|([a, b], [c, d, e])| [hole[*a], hole[*b], board[*c], board[*d], board[*e]])
+}
+
+/// The strength of the best high hand.
+pub fn best_high(hole: &[Card; HOLE_SIZE], board: &[Card; BOARD_SIZE]) -> Strength {
+    hands(hole, board).map(|hand| best_strength(&hand)).max().unwrap()
+}
+
+/// A qualifying eight-or-better low hand: five distinct values of eight or lower, aces counting low.
+/// Straights and flushes do not count against a low. Ordered such that the better (lower) hand is greater.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub struct Low {
+    /// The values (ace counting as one), highest first.
+    ranks: [u8; 5],
+}
+
+impl Low {
+    /// The low of the five cards, if it qualifies.
+    pub fn new(cards: &[Card; 5]) -> Option<Self> {
+        let mut ranks = cards.map(|card| low_rank(card.value));
+        ranks.sort_unstable_by(|a, b| b.cmp(a));
+
+        let qualifies = ranks[0] <= 8 && ranks.windows(2).all(|ranks| ranks[0] != ranks[1]);
+
+        qualifies.then_some(Self { ranks })
+    }
+
+    /// The values of the low, highest first.
+    pub fn values(&self) -> [Value; 5] {
+        self.ranks.map(|rank| match rank {
+            1 => Value::Ace,
+            rank => Value::ALL[rank as usize - 2],
+        })
+    }
+}
+
+/// The value of a card in a low hand, the ace counting as one.
+fn low_rank(value: Value) -> u8 {
+    match value {
+        Value::Ace => 1,
+        value => value as u8 + 2,
+    }
+}
+
+impl PartialOrd for Low {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Low {
+    /// The hand with the lower highest card (then second highest, ...) is better.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.ranks.cmp(&self.ranks)
+    }
+}
+
+impl fmt::Display for Low {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for value in self.values() {
+            write!(formatter, "{value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The best qualifying eight-or-better low, if any.
+pub fn best_low(hole: &[Card; HOLE_SIZE], board: &[Card; BOARD_SIZE]) -> Option<Low> {
+    hands(hole, board).filter_map(|hand| Low::new(&hand)).max()
+}
+
+/// Computes the Omaha (high only) equity of two or more hands, given the (partial) board and dead cards.
+pub fn calculate(hands: &[[Card; HOLE_SIZE]], board: &[Card], dead: &[Card], method: Method) -> Result<equity::Equity, EquityError> {
+    let hands: Vec<&[Card]> = hands.iter().map(|hand| &hand[..]).collect();
+
+    equity::calculate_with(&hands, board, dead, method, |hand, board| best_high(hand.try_into().unwrap(), board))
+}
+
+/// The Omaha Hi/Lo showdown results of one hand.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct HiLoResult {
+    /// The results for the high half of the pot.
+    pub high: HandEquity,
+    /// The results for the low half of the pot, only counting boards on which a low qualifies.
+    pub low: HandEquity,
+    /// The amount of boards the hand wins the whole pot alone.
+    pub scoops: u64,
+    /// The sum of the share of the whole pot won on each board.
+    pub share: f64,
+}
+
+/// The Omaha Hi/Lo equity of each hand.
+#[derive(PartialEq, Debug, Clone)]
+pub struct HiLoEquity {
+    /// In the order the hands were given.
+    pub hands: Vec<HiLoResult>,
+    /// The amount of boards evaluated.
+    pub boards: u64,
+    /// The amount of boards on which a low qualified and the pot was split.
+    pub low_boards: u64,
+    /// Whether all possible boards were enumerated.
+    pub exact: bool,
+}
+
+impl HiLoEquity {
+    /// The probability of the hand winning the whole pot alone.
+    pub fn scoop(&self, hand: usize) -> f64 {
+        self.hands[hand].scoops as f64 / self.boards as f64
+    }
+
+    /// The expected share of the whole pot.
+    pub fn equity(&self, hand: usize) -> f64 {
+        self.hands[hand].share / self.boards as f64
+    }
+}
+
+impl fmt::Display for HiLoEquity {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "{} boards ({}), {} with a low", self.boards, if self.exact { "exact" } else { "Monte Carlo" }, self.low_boards)?;
+        writeln!(formatter, "{:<6} {:>9} {:>9} {:>9} {:>9}", "Hand", "High", "Low", "Scoop", "Equity")?;
+
+        for (index, result) in self.hands.iter().enumerate() {
+            writeln!(
+                formatter, "{:<6} {:>8.4}% {:>8.4}% {:>8.4}% {:>8.4}%",
+                index + 1,
+                result.high.share / self.boards as f64 * 100f64,
+                result.low.share / self.low_boards.max(1) as f64 * 100f64,
+                self.scoop(index) * 100f64,
+                self.equity(index) * 100f64
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Computes the Omaha Hi/Lo (eight or better) equity of two or more hands, given the (partial) board and dead cards.
+/// The pot is split between the best high and the best low hand; without a qualifying low, the high hand takes it all.
+pub fn calculate_hi_lo(hands: &[[Card; HOLE_SIZE]], board: &[Card], dead: &[Card], method: Method) -> Result<HiLoEquity, EquityError> {
+    let slices: Vec<&[Card]> = hands.iter().map(|hand| &hand[..]).collect();
+
+    let mut results = vec![HiLoResult::default(); hands.len()];
+    let mut low_boards = 0u64;
+
+    let (boards, exact) = equity::deal_boards(&slices, board, dead, method, |full| {
+        let highs: Vec<Option<Strength>> = hands.iter().map(|hand| Some(best_high(hand, full))).collect();
+        let lows: Vec<Option<Low>> = hands.iter().map(|hand| best_low(hand, full)).collect();
+
+        let high_shares = equity::pot_shares(&highs);
+        let split = lows.iter().any(Option::is_some);
+
+        let low_shares = match split {
+            true => equity::pot_shares(&lows),
+            false => vec![0f64; hands.len()],
+        };
+
+        low_boards += split as u64;
+
+        for ((result, high), low) in results.iter_mut().zip(high_shares).zip(low_shares) {
+            let share = match split {
+                true => (high + low) / 2f64,
+                false => high,
+            };
+
+            result.high.record(high);
+
+            if split {
+                result.low.record(low);
+            }
+
+            if share == 1f64 {
+                result.scoops += 1;
+            }
+
+            result.share += share;
+        }
+    })?;
+
+    Ok(HiLoEquity { hands: results, boards, low_boards, exact })
+}
@@ -0,0 +1,344 @@
+//! Parsing of cards, hands and ranks from their textual notation.
+//!
+//! Cards are written as a value followed by a suit, optionally separated by whitespace. Values
+//! may be given as `2`-`9`, `T`/`10`, `J`, `Q`, `K`, `A` and suits as `♥♠♣♦` or `HSCD`, both
+//! case-insensitive. Hence `Ah`, `10♠` and `T s` all denote valid cards. The joker is written as
+//! `X` (or `🃏`) without a suit; it is only accepted by [parse_deck_cards] given a deck containing it.
+//! Cards in a hand are separated by whitespace and/or commas.
+
+use core::fmt;
+use std::{collections::HashSet, error::Error, str::FromStr};
+
+use crate::{Card, Deck, Rank, StraightFlushDetails, Suit, Value};
+
+/// The reason why parsing failed.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ParseErrorKind {
+    /// The character is not a valid card value.
+    InvalidValue(char),
+    /// The character is not a valid suit.
+    InvalidSuit(char),
+    /// The character was not expected at this position.
+    UnexpectedCharacter(char),
+    /// The input ended prematurely.
+    UnexpectedEnd,
+    /// The card appears more than once.
+    DuplicateCard(Card),
+    /// The card is not part of the deck, e.g. a joker outside of Joker Poker.
+    NotInDeck(Card),
+    /// The amount of cards does not match the expected amount.
+    CardCount {
+        expected: usize,
+        found: usize
+    },
+    /// The name is not the name of a rank.
+    UnknownRank,
+    /// The end of a hand range does not match its start, e.g. `AKs-QJo`.
+    InvalidRange,
+    /// The weight of a hand range is not a number between 0 and 1.
+    InvalidWeight,
+}
+
+/// An error that occured while parsing, along with the position it occured at.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct ParseError {
+    /// The offset (in characters) into the input.
+    pub position: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::InvalidValue(value) => write!(formatter, "invalid card value `{value}`")?,
+            ParseErrorKind::InvalidSuit(suit) => write!(formatter, "invalid suit `{suit}`")?,
+            ParseErrorKind::UnexpectedCharacter(character) => write!(formatter, "unexpected character `{character}`")?,
+            ParseErrorKind::UnexpectedEnd => write!(formatter, "unexpected end of input")?,
+            ParseErrorKind::DuplicateCard(card) => write!(formatter, "duplicate card `{card}`")?,
+            ParseErrorKind::NotInDeck(card) => write!(formatter, "card `{card}` is not part of the deck")?,
+            ParseErrorKind::CardCount { expected, found } => write!(formatter, "expected {expected} cards, found {found}")?,
+            ParseErrorKind::UnknownRank => write!(formatter, "unknown rank")?,
+            ParseErrorKind::InvalidRange => write!(formatter, "invalid range")?,
+            ParseErrorKind::InvalidWeight => write!(formatter, "invalid weight")?,
+        }
+
+        write!(formatter, " at position {}", self.position)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A simple cursor over the characters of the input.
+pub(crate) struct Parser {
+    pub(crate) chars: Vec<char>,
+    pub(crate) position: usize,
+}
+
+impl Parser {
+    pub(crate) fn new(input: &str) -> Self {
+        Self { chars: input.chars().collect(), position: 0 }
+    }
+
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    pub(crate) fn error(&self, kind: ParseErrorKind) -> ParseError {
+        ParseError { position: self.position, kind }
+    }
+
+    pub(crate) fn next(&mut self) -> Result<char, ParseError> {
+        let next = self.peek().ok_or(self.error(ParseErrorKind::UnexpectedEnd))?;
+        self.position += 1;
+
+        Ok(next)
+    }
+
+    pub(crate) fn skip_while(&mut self, predicate: impl Fn(char) -> bool) {
+        while self.peek().is_some_and(&predicate) {
+            self.position += 1;
+        }
+    }
+
+    pub(crate) fn skip_whitespace(&mut self) {
+        self.skip_while(char::is_whitespace);
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.position >= self.chars.len()
+    }
+
+    pub(crate) fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        self.skip_whitespace();
+
+        let position = self.position;
+        match self.next()? {
+            character if character == expected => Ok(()),
+            character => Err(ParseError { position, kind: ParseErrorKind::UnexpectedCharacter(character) }),
+        }
+    }
+
+    pub(crate) fn expect_end(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(character) => Err(self.error(ParseErrorKind::UnexpectedCharacter(character))),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+
+        let position = self.position;
+        let value = self.next()?;
+
+        if value == '1' {
+            return match self.next()? {
+                '0' => Ok(Value::Ten),
+                _ => Err(ParseError { position, kind: ParseErrorKind::InvalidValue(value) }),
+            };
+        }
+
+        Value::try_from(value).map_err(|value| ParseError { position, kind: ParseErrorKind::InvalidValue(value) })
+    }
+
+    pub(crate) fn suit(&mut self) -> Result<Suit, ParseError> {
+        self.skip_whitespace();
+
+        let position = self.position;
+
+        Suit::try_from(self.next()?).map_err(|suit| ParseError { position, kind: ParseErrorKind::InvalidSuit(suit) })
+    }
+
+    /// Parses a card of any deck, including the joker.
+    pub(crate) fn card(&mut self) -> Result<Card, ParseError> {
+        let value = self.value()?;
+
+        if value == Value::Joker {
+            return Ok(Card::JOKER);
+        }
+
+        let suit = self.suit()?;
+
+        Ok(Card { suit, value })
+    }
+
+    /// Parses a card, which must be part of the given deck.
+    pub(crate) fn deck_card(&mut self, deck: &[Card]) -> Result<Card, ParseError> {
+        self.skip_whitespace();
+
+        let position = self.position;
+        let card = self.card()?;
+
+        match deck.contains(&card) {
+            true => Ok(card),
+            false => Err(ParseError { position, kind: ParseErrorKind::NotInDeck(card) }),
+        }
+    }
+
+    /// Parses cards of the given deck until the end of the input, rejecting duplicates.
+    pub(crate) fn cards(&mut self, deck: &[Card]) -> Result<Vec<Card>, ParseError> {
+        let mut cards = Vec::new();
+        let mut seen = HashSet::new();
+
+        loop {
+            self.skip_while(|character| character.is_whitespace() || character == ',');
+
+            if self.is_done() {
+                return Ok(cards);
+            }
+
+            let position = self.position;
+            let card = self.deck_card(deck)?;
+
+            if !seen.insert(card) {
+                return Err(ParseError { position, kind: ParseErrorKind::DuplicateCard(card) });
+            }
+
+            cards.push(card);
+        }
+    }
+
+    pub(crate) fn name(&mut self) -> String {
+        self.skip_whitespace();
+
+        let start = self.position;
+        self.skip_while(char::is_alphabetic);
+
+        self.chars[start..self.position].iter().collect()
+    }
+}
+
+/// Parses a list of distinct cards of the standard deck, e.g. `Ah Kh Qh Jh 10h`.
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse_deck_cards(input, &Deck::standard().cards)
+}
+
+/// Parses a list of distinct cards, each of which must be part of the given deck (e.g. [Deck::with_joker]).
+pub fn parse_deck_cards(input: &str, deck: &[Card]) -> Result<Vec<Card>, ParseError> {
+    Parser::new(input).cards(deck)
+}
+
+/// Parses the five distinct cards of a hand of the standard deck, e.g. `Ah Kh Qh Jh 10h`.
+pub fn parse_hand(input: &str) -> Result<[Card; 5], ParseError> {
+    parse_deck_hand(input, &Deck::standard().cards)
+}
+
+/// Parses the five distinct cards of a hand, each of which must be part of the given deck (e.g. [Deck::with_joker]).
+pub fn parse_deck_hand(input: &str, deck: &[Card]) -> Result<[Card; 5], ParseError> {
+    fixed_cards(input, deck)
+}
+
+/// Parses exactly `N` distinct cards of the given deck.
+fn fixed_cards<const N: usize>(input: &str, deck: &[Card]) -> Result<[Card; N], ParseError> {
+    let mut parser = Parser::new(input);
+    let cards = parser.cards(deck)?;
+    let found = cards.len();
+
+    cards
+        .try_into()
+        .map_err(|_| ParseError { position: parser.position, kind: ParseErrorKind::CardCount { expected: N, found } })
+}
+
+impl FromStr for Suit {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(input);
+        let suit = parser.suit()?;
+        parser.expect_end()?;
+
+        Ok(suit)
+    }
+}
+
+impl FromStr for Value {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(input);
+        let value = parser.value()?;
+        parser.expect_end()?;
+
+        Ok(value)
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseError;
+
+    /// Parses a card of the standard deck; the joker is only accepted by [parse_deck_cards].
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(input);
+        let card = parser.deck_card(&Deck::standard().cards)?;
+        parser.expect_end()?;
+
+        Ok(card)
+    }
+}
+
+impl<const N: usize> FromStr for Deck<N> {
+    type Err = ParseError;
+
+    /// Parses distinct cards of the standard deck.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Ok(Deck { cards: fixed_cards(input, &Deck::standard().cards)? })
+    }
+}
+
+impl FromStr for Rank {
+    type Err = ParseError;
+
+    /// Parses a rank in the notation produced by its [fmt::Display] implementation, e.g. `TwoPair(K, 7)`.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(input);
+
+        let start = parser.position;
+        let name = parser.name();
+
+        parser.expect('(')?;
+
+        let rank = match name.to_lowercase().as_str() {
+            "highcard" => Rank::HighCard(parser.value()?),
+            "pair" => Rank::Pair(parser.value()?),
+            "twopair" => {
+                let a = parser.value()?;
+                parser.expect(',')?;
+                let b = parser.value()?;
+
+                Rank::TwoPair { a, b }
+            },
+            "threeofakind" => Rank::ThreeOfAKind(parser.value()?),
+            "straight" => {
+                let Card { suit, value } = parser.deck_card(&Deck::standard().cards)?;
+
+                Rank::Straight { high: value, suit }
+            },
+            "flush" => Rank::Flush(parser.value()?),
+            "fullhouse" => {
+                let three_of_a_kind = parser.value()?;
+                parser.expect(',')?;
+                let pair = parser.value()?;
+
+                Rank::FullHouse { three_of_a_kind, pair }
+            },
+            "fourofakind" => {
+                let value = parser.value()?;
+                parser.expect(',')?;
+                let kicker = parser.value()?;
+
+                Rank::FourOfAKind { value, kicker }
+            },
+            "straightflush" => Rank::StraightFlush(StraightFlushDetails::from(parser.deck_card(&Deck::standard().cards)?)),
+            "royalflush" => Rank::RoyalFlush(parser.suit()?),
+            "fiveofakind" => Rank::FiveOfAKind(parser.value()?),
+            _ => return Err(ParseError { position: start, kind: ParseErrorKind::UnknownRank }),
+        };
+
+        parser.expect(')')?;
+        parser.expect_end()?;
+
+        Ok(rank)
+    }
+}
@@ -0,0 +1,319 @@
+//! Paytables, i.e. the payout of each winning hand.
+
+use core::fmt;
+
+use serde::{Serialize, Deserialize};
+
+use crate::{Evaluation, Rank, Value};
+
+/// A set of card values, e.g. the values of four of a kind paying a bonus.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<Value>", into = "Vec<Value>")]
+pub struct ValueSet(u16);
+
+impl ValueSet {
+    /// All values from `min` up to and including `max`.
+    pub fn range(min: Value, max: Value) -> Self {
+        Value::ALL.iter().copied().filter(|&value| value >= min && value <= max).collect()
+    }
+
+    pub fn contains(&self, value: Value) -> bool {
+        self.0 & (1 << value as u16) != 0
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = Value> + '_ {
+        Value::ALL.iter().copied().filter(|&value| self.contains(value))
+    }
+}
+
+impl FromIterator<Value> for ValueSet {
+    fn from_iter<T: IntoIterator<Item = Value>>(values: T) -> Self {
+        Self(values.into_iter().fold(0, |set, value| set | 1 << value as u16))
+    }
+}
+
+impl From<Vec<Value>> for ValueSet {
+    fn from(values: Vec<Value>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl From<ValueSet> for Vec<Value> {
+    fn from(set: ValueSet) -> Self {
+        set.values().collect()
+    }
+}
+
+impl fmt::Display for ValueSet {
+    /// Formats runs of consecutive values as ranges, e.g. `2-4, A`.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values: Vec<Value> = self.values().collect();
+        let mut first = true;
+
+        for run in values.chunk_by(|a, b| *a as usize + 1 == *b as usize) {
+            if !first {
+                write!(formatter, ", ")?;
+            }
+
+            match run {
+                [value] => write!(formatter, "{value}")?,
+                [low, .., high] => write!(formatter, "{low}-{high}")?,
+                [] => unreachable!(),
+            }
+
+            first = false;
+        }
+
+        Ok(())
+    }
+}
+
+/// A category of winning hands.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum Category {
+    /// A pair of at least the given value.
+    HighPair { min: Value },
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    /// Four of a kind of one of the given values, optionally only with a kicker of one of the given values.
+    /// Used by the Bonus Poker family of games, which pay extra for certain quads.
+    SpecificFourOfAKind { values: ValueSet, kickers: Option<ValueSet> },
+    StraightFlush,
+    /// A royal flush formed with at least one wild card.
+    WildRoyalFlush,
+    /// A royal flush formed without wild cards.
+    RoyalFlush,
+    FiveOfAKind,
+    /// All four deuces (when deuces are wild).
+    FourDeuces,
+}
+
+impl Category {
+    /// Whether a hand of the given evaluation falls into this category.
+    pub fn matches(&self, evaluation: Evaluation) -> bool {
+        let Evaluation { rank, wilds } = evaluation;
+
+        match (self, rank) {
+            (Category::FourDeuces, _) => wilds == 4,
+            (Category::WildRoyalFlush, Rank::RoyalFlush(_)) => wilds != 0,
+            (Category::RoyalFlush, Rank::RoyalFlush(_)) => wilds == 0,
+            (Category::HighPair { min }, Rank::Pair(value)) => value >= *min,
+            (Category::SpecificFourOfAKind { values, kickers }, Rank::FourOfAKind { value, kicker }) => {
+                values.contains(value) && kickers.is_none_or(|kickers| kickers.contains(kicker))
+            },
+            (Category::TwoPair, Rank::TwoPair { .. }) |
+            (Category::ThreeOfAKind, Rank::ThreeOfAKind(_)) |
+            (Category::Straight, Rank::Straight { .. }) |
+            (Category::Flush, Rank::Flush(_)) |
+            (Category::FullHouse, Rank::FullHouse { .. }) |
+            (Category::FourOfAKind, Rank::FourOfAKind { .. }) |
+            (Category::StraightFlush, Rank::StraightFlush(_)) |
+            (Category::FiveOfAKind, Rank::FiveOfAKind(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Category::HighPair { min: Value::Jack } => write!(formatter, "Jacks or Better"),
+            Category::HighPair { min: Value::Queen } => write!(formatter, "Queens or Better"),
+            Category::HighPair { min: Value::King } => write!(formatter, "Kings or Better"),
+            Category::HighPair { min: Value::Ace } => write!(formatter, "Pair of Aces"),
+            Category::HighPair { min } => write!(formatter, "Pair of {min}s or Better"),
+            Category::TwoPair => write!(formatter, "Two Pair"),
+            Category::ThreeOfAKind => write!(formatter, "Three of a Kind"),
+            Category::Straight => write!(formatter, "Straight"),
+            Category::Flush => write!(formatter, "Flush"),
+            Category::FullHouse => write!(formatter, "Full House"),
+            Category::FourOfAKind => write!(formatter, "Four of a Kind"),
+            Category::SpecificFourOfAKind { values, kickers: None } => write!(formatter, "Four {values}"),
+            Category::SpecificFourOfAKind { values, kickers: Some(kickers) } => write!(formatter, "Four {values} + {kickers}"),
+            Category::StraightFlush => write!(formatter, "Straight Flush"),
+            Category::WildRoyalFlush => write!(formatter, "Wild Royal Flush"),
+            Category::RoyalFlush => write!(formatter, "Royal Flush"),
+            Category::FiveOfAKind => write!(formatter, "Five of a Kind"),
+            Category::FourDeuces => write!(formatter, "Four Deuces"),
+        }
+    }
+}
+
+/// The payout (per coin bet) of a category.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Serialize, Deserialize)]
+pub struct Payout {
+    pub category: Category,
+    pub payout: usize,
+}
+
+/// A paytable of a video poker game.
+#[derive(PartialEq, Eq, Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct Paytable {
+    pub name: String,
+    /// Ordered from the best to the worst category; a hand is paid by the first category it matches.
+    pub payouts: Vec<Payout>,
+}
+
+impl Paytable {
+    /// Creates a paytable from `(category, payout)` pairs, best category first.
+    pub fn new(name: impl Into<String>, payouts: &[(Category, usize)]) -> Self {
+        Self {
+            name: name.into(),
+            payouts: payouts.iter().map(|&(category, payout)| Payout { category, payout }).collect(),
+        }
+    }
+
+    /// Full-pay "9/6" Jacks or Better, paying 800 for a royal flush (as when playing max coins).
+    pub fn jacks_or_better_9_6() -> Self {
+        Self::jacks_or_better("jacks-or-better-9-6", 800, 9, 6)
+    }
+
+    /// Jacks or Better in accordance with [win2day](https://www.win2day.at/fairplay/spielbedingungen/jacksorbetter-spielbedingungen).
+    pub fn win2day() -> Self {
+        Self::jacks_or_better("win2day", 250, 9, 6)
+    }
+
+    fn jacks_or_better(name: &str, royal_flush: usize, full_house: usize, flush: usize) -> Self {
+        Self::new(name, &[
+            (Category::RoyalFlush, royal_flush),
+            (Category::StraightFlush, 50),
+            (Category::FourOfAKind, 25),
+            (Category::FullHouse, full_house),
+            (Category::Flush, flush),
+            (Category::Straight, 4),
+            (Category::ThreeOfAKind, 3),
+            (Category::TwoPair, 2),
+            (Category::HighPair { min: Value::Jack }, 1),
+        ])
+    }
+
+    /// Bonus Poker paying 8/5 for full house and flush, with bonuses for four aces and four 2-4.
+    pub fn bonus_poker_8_5() -> Self {
+        Self::bonus_poker("bonus-poker-8-5", &[
+            (Category::SpecificFourOfAKind { values: ValueSet::range(Value::Ace, Value::Ace), kickers: None }, 80),
+            (Category::SpecificFourOfAKind { values: ValueSet::range(Value::Two, Value::Four), kickers: None }, 40),
+        ], [25, 8, 5, 4, 2])
+    }
+
+    /// Double Bonus Poker paying 10/7 for full house and flush, the full pay version.
+    pub fn double_bonus_10_7() -> Self {
+        Self::bonus_poker("double-bonus-10-7", &[
+            (Category::SpecificFourOfAKind { values: ValueSet::range(Value::Ace, Value::Ace), kickers: None }, 160),
+            (Category::SpecificFourOfAKind { values: ValueSet::range(Value::Two, Value::Four), kickers: None }, 80),
+        ], [50, 10, 7, 5, 1])
+    }
+
+    /// Double Double Bonus Poker paying 9/6 for full house and flush, with extra bonuses for
+    /// four aces with a 2-4 kicker and four 2-4 with an ace or 2-4 kicker.
+    pub fn double_double_bonus_9_6() -> Self {
+        let aces = ValueSet::range(Value::Ace, Value::Ace);
+        let low = ValueSet::range(Value::Two, Value::Four);
+        let low_or_aces: ValueSet = low.values().chain([Value::Ace]).collect();
+
+        Self::bonus_poker("double-double-bonus-9-6", &[
+            (Category::SpecificFourOfAKind { values: aces, kickers: Some(low) }, 400),
+            (Category::SpecificFourOfAKind { values: low, kickers: Some(low_or_aces) }, 160),
+            (Category::SpecificFourOfAKind { values: aces, kickers: None }, 160),
+            (Category::SpecificFourOfAKind { values: low, kickers: None }, 80),
+        ], [50, 9, 6, 4, 1])
+    }
+
+    /// A game of the Bonus Poker family: Jacks or Better with the given quad bonuses (best first)
+    /// and the payouts of the other four of a kind, full house, flush, straight and two pair.
+    fn bonus_poker(name: &str, bonuses: &[(Category, usize)], payouts: [usize; 5]) -> Self {
+        let [four_of_a_kind, full_house, flush, straight, two_pair] = payouts;
+
+        let mut payouts = vec![(Category::RoyalFlush, 800), (Category::StraightFlush, 50)];
+        payouts.extend_from_slice(bonuses);
+        payouts.extend_from_slice(&[
+            (Category::FourOfAKind, four_of_a_kind),
+            (Category::FullHouse, full_house),
+            (Category::Flush, flush),
+            (Category::Straight, straight),
+            (Category::ThreeOfAKind, 3),
+            (Category::TwoPair, two_pair),
+            (Category::HighPair { min: Value::Jack }, 1),
+        ]);
+
+        Self::new(name, &payouts)
+    }
+
+    /// Full-pay Deuces Wild, paying 800 for a natural royal flush.
+    pub fn deuces_wild_full_pay() -> Self {
+        Self::new("deuces-wild-full-pay", &[
+            (Category::RoyalFlush, 800),
+            (Category::FourDeuces, 200),
+            (Category::WildRoyalFlush, 25),
+            (Category::FiveOfAKind, 15),
+            (Category::StraightFlush, 9),
+            (Category::FourOfAKind, 5),
+            (Category::FullHouse, 3),
+            (Category::Flush, 2),
+            (Category::Straight, 2),
+            (Category::ThreeOfAKind, 1),
+        ])
+    }
+
+    /// Joker Poker (kings or better) with the full 20/7/5 payouts for four of a kind, full house and flush.
+    pub fn joker_poker_kings() -> Self {
+        Self::new("joker-poker-kings", &[
+            (Category::RoyalFlush, 800),
+            (Category::FiveOfAKind, 200),
+            (Category::WildRoyalFlush, 100),
+            (Category::StraightFlush, 50),
+            (Category::FourOfAKind, 20),
+            (Category::FullHouse, 7),
+            (Category::Flush, 5),
+            (Category::Straight, 3),
+            (Category::ThreeOfAKind, 2),
+            (Category::TwoPair, 1),
+            (Category::HighPair { min: Value::King }, 1),
+        ])
+    }
+
+    /// All built-in paytables.
+    pub fn presets() -> Vec<Self> {
+        vec![
+            Self::win2day(),
+            Self::jacks_or_better_9_6(),
+            Self::bonus_poker_8_5(),
+            Self::double_bonus_10_7(),
+            Self::double_double_bonus_9_6(),
+            Self::deuces_wild_full_pay(),
+            Self::joker_poker_kings(),
+        ]
+    }
+
+    /// Finds a built-in paytable by its name.
+    pub fn preset(name: &str) -> Option<Self> {
+        Self::presets().into_iter().find(|paytable| paytable.name == name)
+    }
+
+    /// The index (into [Paytable::payouts]) of the category the hand is paid by, if any.
+    pub fn category(&self, evaluation: impl Into<Evaluation>) -> Option<usize> {
+        let evaluation = evaluation.into();
+
+        self.payouts.iter().position(|payout| payout.category.matches(evaluation))
+    }
+
+    /// Calculates the score (payout per coin) of a hand of the given rank or evaluation.
+    pub fn score(&self, evaluation: impl Into<Evaluation>) -> usize {
+        self.category(evaluation).map_or(0, |index| self.payouts[index].payout)
+    }
+}
+
+impl fmt::Display for Paytable {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "{}:", self.name)?;
+
+        for payout in &self.payouts {
+            writeln!(formatter, "  {:<20} {:>5}", payout.category.to_string(), payout.payout)?;
+        }
+
+        Ok(())
+    }
+}
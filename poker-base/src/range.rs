@@ -0,0 +1,450 @@
+//! Hold'em hand ranges in the standard notation and their equity against each other.
+//!
+//! A range is a comma separated list of hands: pairs (`TT`), suited (`AKs`), offsuit (`KQo`) or
+//! both (`AK`) non-pairs and specific combos (`AhKh`). A `+` extends a pair up to aces (`TT+`) and
+//! a non-pair up to one below its high card (`A2s+`), while `-` spans two hands sharing their high
+//! card (`A2s-A5s`, `TT-77`). Any hand may be weighted with a trailing `:<weight>` (`AKo:0.5`).
+
+use core::fmt;
+use std::str::FromStr;
+
+use itertools::Itertools;
+use rand::{distributions::WeightedIndex, prelude::Distribution, rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::{
+    equity::{self, EquityError, HandEquity, Method, BOARD_SIZE, DEFAULT_TRIALS, EXACT_LIMIT},
+    parse::Parser,
+    Card, Deck, ParseError, ParseErrorKind, Suit, Value,
+};
+
+/// Two hole cards and how often they are played, from 0 (never) to 1 (always).
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct WeightedCombo {
+    /// The higher card first.
+    pub cards: [Card; 2],
+    pub weight: f64,
+}
+
+/// A set of hole card combos, e.g. the hands an opponent plays.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct Range {
+    pub combos: Vec<WeightedCombo>,
+}
+
+/// Orders the cards of a combo, the higher one (by value, then suit) first.
+fn combo(a: Card, b: Card) -> [Card; 2] {
+    match (a.value, a.suit.index()) >= (b.value, b.suit.index()) {
+        true => [a, b],
+        false => [b, a],
+    }
+}
+
+/// Whether the hand notation is suited (`s`), offsuit (`o`) or both.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+enum Suitedness {
+    Suited,
+    Offsuit,
+    Any,
+}
+
+/// A hand of the notation, e.g. `AKs` or `TT`.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+struct Hand {
+    high: Value,
+    low: Value,
+    suitedness: Suitedness,
+}
+
+impl Hand {
+    fn combos(&self) -> Vec<[Card; 2]> {
+        let suits = Suit::ALL.iter().copied().cartesian_product(Suit::ALL);
+
+        suits
+            .filter(|&(a, b)| match self.high == self.low {
+                true => a.index() < b.index(),
+                false => match self.suitedness {
+                    Suitedness::Suited => a == b,
+                    Suitedness::Offsuit => a != b,
+                    Suitedness::Any => true,
+                },
+            })
+            .map(|(a, b)| combo(Card { suit: a, value: self.high }, Card { suit: b, value: self.low }))
+            .collect()
+    }
+
+    /// The hands from `self` to `end` (inclusive), which must be the same kind of hand.
+    fn span(self, end: Hand) -> Option<Vec<Hand>> {
+        let pair = self.high == self.low;
+
+        if pair != (end.high == end.low) || self.suitedness != end.suitedness || (!pair && self.high != end.high) {
+            return None;
+        }
+
+        let (from, to) = match pair {
+            true => (self.high.min(end.high), self.high.max(end.high)),
+            false => (self.low.min(end.low), self.low.max(end.low)),
+        };
+
+        let hands = Value::ALL
+            .into_iter()
+            .filter(|&value| value >= from && value <= to)
+            .map(|value| match pair {
+                true => Hand { high: value, low: value, ..self },
+                false => Hand { low: value, ..self },
+            })
+            .collect();
+
+        Some(hands)
+    }
+
+    /// The hands from `self` up to aces (pairs) or one below the high card (non-pairs).
+    fn upwards(self) -> Vec<Hand> {
+        let end = match self.high == self.low {
+            true => Hand { high: Value::Ace, low: Value::Ace, ..self },
+            false => Hand { low: Value::ALL[self.high as usize - 1].max(self.low), ..self },
+        };
+
+        self.span(end).unwrap()
+    }
+}
+
+impl Parser {
+    /// Parses a value of a range, which may not be a joker.
+    fn range_value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+
+        let position = self.position;
+
+        match self.value()? {
+            Value::Joker => Err(ParseError { position, kind: ParseErrorKind::NotInDeck(Card::JOKER) }),
+            value => Ok(value),
+        }
+    }
+
+    /// Parses a hand of the notation after its first value.
+    fn hand(&mut self, first: Value) -> Result<Hand, ParseError> {
+        let second = self.range_value()?;
+
+        let suitedness = match self.peek() {
+            Some('s' | 'S') if first != second => Suitedness::Suited,
+            Some('o' | 'O') if first != second => Suitedness::Offsuit,
+            _ => Suitedness::Any,
+        };
+
+        if suitedness != Suitedness::Any {
+            self.position += 1;
+        }
+
+        Ok(Hand { high: first.max(second), low: first.min(second), suitedness })
+    }
+
+    /// Parses the combos of a single entry of a range, without its weight.
+    fn range_entry(&mut self) -> Result<Vec<[Card; 2]>, ParseError> {
+        self.skip_whitespace();
+
+        let first = self.range_value()?;
+
+        // a suit following the first value denotes a specific combo, e.g. `AhKh`.
+        if self.peek().is_some_and(|character| Suit::try_from(character).is_ok()) {
+            let suit = self.suit()?;
+            let position = self.position;
+            let second = self.deck_card(&Deck::standard().cards)?;
+            let first = Card { suit, value: first };
+
+            if first == second {
+                return Err(ParseError { position, kind: ParseErrorKind::DuplicateCard(first) });
+            }
+
+            return Ok(vec![combo(first, second)]);
+        }
+
+        let hand = self.hand(first)?;
+
+        let hands = match self.peek() {
+            Some('+') => {
+                self.position += 1;
+
+                hand.upwards()
+            },
+            Some('-') => {
+                self.position += 1;
+
+                let position = self.position;
+                let first = self.range_value()?;
+                let end = self.hand(first)?;
+
+                hand.span(end).ok_or(ParseError { position, kind: ParseErrorKind::InvalidRange })?
+            },
+            _ => vec![hand],
+        };
+
+        Ok(hands.iter().flat_map(Hand::combos).collect())
+    }
+
+    fn weight(&mut self) -> Result<f64, ParseError> {
+        self.skip_whitespace();
+
+        let position = self.position;
+        self.skip_while(|character| character.is_ascii_digit() || character == '.');
+
+        let weight: String = self.chars[position..self.position].iter().collect();
+
+        match weight.parse::<f64>() {
+            Ok(weight) if (0f64..=1f64).contains(&weight) => Ok(weight),
+            _ => Err(ParseError { position, kind: ParseErrorKind::InvalidWeight }),
+        }
+    }
+}
+
+impl FromStr for Range {
+    type Err = ParseError;
+
+    /// Parses a range such as `TT+, A2s-A5s, KQo:0.5, AhKh`. Later entries override the weight of earlier ones.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(input);
+        let mut range = Range::default();
+
+        loop {
+            parser.skip_while(|character| character.is_whitespace() || character == ',');
+
+            if parser.is_done() {
+                return Ok(range);
+            }
+
+            let combos = parser.range_entry()?;
+
+            parser.skip_whitespace();
+
+            let weight = match parser.peek() {
+                Some(':') => {
+                    parser.position += 1;
+
+                    parser.weight()?
+                },
+                _ => 1f64,
+            };
+
+            for cards in combos {
+                range.insert(WeightedCombo { cards, weight });
+            }
+        }
+    }
+}
+
+impl Range {
+    /// Adds the combo, replacing the weight of the combo if already contained.
+    pub fn insert(&mut self, combo: WeightedCombo) {
+        match self.combos.iter_mut().find(|contained| contained.cards == combo.cards) {
+            Some(contained) => contained.weight = combo.weight,
+            None => self.combos.push(combo),
+        }
+    }
+
+    /// The range without the combos containing any of the given (known) cards.
+    pub fn without(&self, removed: &[Card]) -> Self {
+        let combos = self.combos
+            .iter()
+            .filter(|combo| combo.weight > 0f64 && !combo.cards.iter().any(|card| removed.contains(card)))
+            .copied()
+            .collect();
+
+        Self { combos }
+    }
+
+    /// The weighted amount of combos.
+    pub fn size(&self) -> f64 {
+        self.combos.iter().map(|combo| combo.weight).sum()
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, combo) in self.combos.iter().enumerate() {
+            if index != 0 {
+                write!(formatter, ", ")?;
+            }
+
+            write!(formatter, "{}{}", combo.cards[0], combo.cards[1])?;
+
+            if combo.weight != 1f64 {
+                write!(formatter, ":{}", combo.weight)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The showdown results of a range, as probabilities.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub struct RangeResult {
+    pub win: f64,
+    pub tie: f64,
+    /// The expected share of the pot, ties splitting it equally.
+    pub equity: f64,
+}
+
+/// The equity of each range against the others.
+#[derive(PartialEq, Debug, Clone)]
+pub struct RangeEquity {
+    /// In the order the ranges were given.
+    pub ranges: Vec<RangeResult>,
+    /// The amount of deals (combos and board) evaluated.
+    pub deals: u64,
+    /// Whether all possible deals were enumerated.
+    pub exact: bool,
+}
+
+impl fmt::Display for RangeEquity {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(formatter, "{} deals ({})", self.deals, if self.exact { "exact" } else { "Monte Carlo" })?;
+        writeln!(formatter, "{:<6} {:>9} {:>9} {:>9}", "Range", "Win", "Tie", "Equity")?;
+
+        for (index, result) in self.ranges.iter().enumerate() {
+            writeln!(formatter, "{:<6} {:>8.4}% {:>8.4}% {:>8.4}%", index + 1, result.win * 100f64, result.tie * 100f64, result.equity * 100f64)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether the combos share a card.
+fn conflicting(combos: &[[Card; 2]]) -> bool {
+    combos.iter().flatten().duplicates().next().is_some()
+}
+
+/// Enumerates every combination of combos (weighted by the product of their weights) and every board.
+fn enumerate(ranges: &[Range], board: &[Card], dead: &[Card]) -> Result<RangeEquity, EquityError> {
+    let mut results = vec![RangeResult::default(); ranges.len()];
+    let mut total = 0f64;
+    let mut deals = 0u64;
+
+    for combos in ranges.iter().map(|range| range.combos.iter()).multi_cartesian_product() {
+        let hands: Vec<[Card; 2]> = combos.iter().map(|combo| combo.cards).collect();
+
+        if conflicting(&hands) {
+            continue;
+        }
+
+        let weight: f64 = combos.iter().map(|combo| combo.weight).product();
+        let equity = equity::calculate(&hands, board, dead, Method::Exact)?;
+
+        for (index, result) in results.iter_mut().enumerate() {
+            result.win += weight * equity.win(index);
+            result.tie += weight * equity.tie(index);
+            result.equity += weight * equity.equity(index);
+        }
+
+        total += weight;
+        deals += equity.boards;
+    }
+
+    if total == 0f64 {
+        return Err(EquityError::ConflictingRanges);
+    }
+
+    for result in &mut results {
+        result.win /= total;
+        result.tie /= total;
+        result.equity /= total;
+    }
+
+    Ok(RangeEquity { ranges: results, deals, exact: true })
+}
+
+/// Deals random combos (by their weights) and boards.
+fn sample(ranges: &[Range], board: &[Card], dead: &[Card], trials: u64, seed: u64) -> Result<RangeEquity, EquityError> {
+    // gives up if the combos keep sharing cards, as the ranges (almost) never fit together.
+    const ATTEMPTS: usize = 10_000;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let distributions: Vec<WeightedIndex<f64>> = ranges
+        .iter()
+        .enumerate()
+        .map(|(index, range)| WeightedIndex::new(range.combos.iter().map(|combo| combo.weight)).map_err(|_| EquityError::EmptyRange(index)))
+        .collect::<Result<_, _>>()?;
+
+    let mut results = vec![HandEquity::default(); ranges.len()];
+    let mut hands: Vec<[Card; 2]> = Vec::with_capacity(ranges.len());
+    let mut strengths = Vec::with_capacity(ranges.len());
+    let mut full = [Card::JOKER; BOARD_SIZE];
+    full[..board.len()].copy_from_slice(board);
+
+    for _ in 0..trials {
+        let mut attempts = 0;
+
+        loop {
+            hands.clear();
+            hands.extend(ranges.iter().zip(&distributions).map(|(range, distribution)| range.combos[distribution.sample(&mut rng)].cards));
+
+            if !conflicting(&hands) {
+                break;
+            }
+
+            attempts += 1;
+
+            if attempts == ATTEMPTS {
+                return Err(EquityError::ConflictingRanges);
+            }
+        }
+
+        let slices: Vec<&[Card]> = hands.iter().map(|hand| &hand[..]).collect();
+        let mut deck = equity::remaining(&slices, board, dead)?;
+
+        if deck.len() < BOARD_SIZE - board.len() {
+            return Err(EquityError::NotEnoughCards);
+        }
+
+        let (dealt, _) = deck.partial_shuffle(&mut rng, BOARD_SIZE - board.len());
+        full[board.len()..].copy_from_slice(dealt);
+
+        strengths.clear();
+        strengths.extend(hands.iter().map(|hand| equity::holdem_strength(hand, &full)));
+
+        equity::showdown(&mut results, &strengths);
+    }
+
+    let ranges = results
+        .iter()
+        .map(|result| RangeResult {
+            win: result.wins as f64 / trials as f64,
+            tie: result.ties as f64 / trials as f64,
+            equity: result.share / trials as f64,
+        })
+        .collect();
+
+    Ok(RangeEquity { ranges, deals: trials, exact: false })
+}
+
+/// Computes the Hold'em equity of two or more ranges against each other, given the (partial) board and
+/// dead cards. Combos containing known cards are removed from the ranges, and combos of different ranges
+/// sharing a card are never dealt together.
+pub fn calculate(ranges: &[Range], board: &[Card], dead: &[Card], method: Method) -> Result<RangeEquity, EquityError> {
+    if ranges.len() < 2 {
+        return Err(EquityError::TooFewHands);
+    }
+
+    if board.len() > BOARD_SIZE {
+        return Err(EquityError::BoardSize(board.len()));
+    }
+
+    let known: Vec<Card> = board.iter().chain(dead).copied().collect();
+    let ranges: Vec<Range> = ranges.iter().map(|range| range.without(&known)).collect();
+
+    if let Some(index) = ranges.iter().position(|range| range.combos.is_empty()) {
+        return Err(EquityError::EmptyRange(index));
+    }
+
+    // `None` if the amount overflows, which is far beyond the limit of an exact enumeration.
+    let deals = ranges.iter().try_fold(
+        equity::binomial(52usize.saturating_sub(2 * ranges.len() + known.len()), BOARD_SIZE - board.len()),
+        |deals, range| deals.checked_mul(range.combos.len() as u64),
+    );
+
+    match method {
+        Method::Exact => enumerate(&ranges, board, dead),
+        Method::Auto if deals.is_some_and(|deals| deals <= EXACT_LIMIT) => enumerate(&ranges, board, dead),
+        Method::Auto => sample(&ranges, board, dead, DEFAULT_TRIALS, 0),
+        Method::MonteCarlo { trials, seed } => sample(&ranges, board, dead, trials, seed),
+    }
+}
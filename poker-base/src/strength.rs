@@ -0,0 +1,149 @@
+//! Comparable hand strengths for games where hands are played against each other (e.g. Hold'em),
+//! including the kickers deciding between hands of the same rank.
+
+use core::fmt;
+
+use crate::{Card, Suit, Value};
+
+/// The category of a poker hand, from worst to best. A royal flush is the best straight flush.
+#[derive(PartialOrd, Ord, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum HandCategory {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl fmt::Display for HandCategory {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandCategory::HighCard => write!(formatter, "High Card"),
+            HandCategory::Pair => write!(formatter, "Pair"),
+            HandCategory::TwoPair => write!(formatter, "Two Pair"),
+            HandCategory::ThreeOfAKind => write!(formatter, "Three of a Kind"),
+            HandCategory::Straight => write!(formatter, "Straight"),
+            HandCategory::Flush => write!(formatter, "Flush"),
+            HandCategory::FullHouse => write!(formatter, "Full House"),
+            HandCategory::FourOfAKind => write!(formatter, "Four of a Kind"),
+            HandCategory::StraightFlush => write!(formatter, "Straight Flush"),
+        }
+    }
+}
+
+/// The strength of the best five card hand, ordered like poker hands: by category, then by the values
+/// deciding ties (e.g. the pair followed by the kickers). Unused values are [Value::Two].
+#[derive(PartialOrd, Ord, PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub struct Strength {
+    pub category: HandCategory,
+    pub values: [Value; 5],
+}
+
+impl Strength {
+    fn new(category: HandCategory, values: &[Value]) -> Self {
+        let mut padded = [Value::Two; 5];
+        padded[..values.len()].copy_from_slice(values);
+
+        Self { category, values: padded }
+    }
+}
+
+impl fmt::Display for Strength {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{} (", self.category)?;
+
+        for value in self.values {
+            write!(formatter, "{value}")?;
+        }
+
+        write!(formatter, ")")
+    }
+}
+
+/// The bit of a value in a value mask.
+const fn bit(value: Value) -> u16 {
+    1 << value as u16
+}
+
+/// The values of a value mask, highest first.
+fn values(mask: u16) -> impl Iterator<Item = Value> {
+    Value::ALL.into_iter().rev().filter(move |&value| mask & bit(value) != 0)
+}
+
+/// The highest card of the best straight within the values of the mask, the ace also counting low.
+fn straight_high(mask: u16) -> Option<Value> {
+    Value::ALL[3..].iter().rev().copied().find(|&high| {
+        let run = match high {
+            Value::Five => bit(Value::Ace) | 0b1111,
+            _ => 0b11111 << (high as u16 - 4),
+        };
+
+        mask & run == run
+    })
+}
+
+/// Computes the strength of the best five card hand formed by any of the given (five to seven) cards.
+/// # Panics
+/// if fewer than five cards or a joker is given.
+pub fn best_strength(cards: &[Card]) -> Strength {
+    assert!(cards.len() >= 5, "at least five cards are required");
+
+    let mut counts = [0u8; 13];
+    let mut suits = [0u16; 4];
+
+    for card in cards {
+        assert!(!card.is_joker(), "jokers cannot be evaluated");
+
+        counts[card.value as usize] += 1;
+        suits[card.suit.index()] |= bit(card.value);
+    }
+
+    let flush = Suit::ALL.iter().map(|suit| suits[suit.index()]).find(|mask| mask.count_ones() >= 5);
+
+    if let Some(high) = flush.and_then(straight_high) {
+        return Strength::new(HandCategory::StraightFlush, &[high]);
+    }
+
+    // the values with the given amount of cards, highest first.
+    let kinds = |amount: u8| Value::ALL.into_iter().rev().filter(move |&value| counts[value as usize] == amount);
+    let mask = Value::ALL.iter().filter(|&&value| counts[value as usize] != 0).fold(0, |mask, &value| mask | bit(value));
+    let kickers = |excluded: &[Value], amount: usize| values(mask).filter(|value| !excluded.contains(value)).take(amount).collect::<Vec<_>>();
+
+    if let Some(quads) = kinds(4).next() {
+        return Strength::new(HandCategory::FourOfAKind, &[&[quads][..], &kickers(&[quads], 1)].concat());
+    }
+
+    let trips: Vec<Value> = kinds(3).collect();
+    let pairs: Vec<Value> = kinds(2).collect();
+
+    if let Some(&three) = trips.first() {
+        // the pair of a full house may also stem from a second three of a kind.
+        let pair = trips.get(1).copied().into_iter().chain(pairs.iter().copied()).max();
+
+        if let Some(pair) = pair {
+            return Strength::new(HandCategory::FullHouse, &[three, pair]);
+        }
+    }
+
+    if let Some(flush) = flush {
+        return Strength::new(HandCategory::Flush, &values(flush).take(5).collect::<Vec<_>>());
+    }
+
+    if let Some(high) = straight_high(mask) {
+        return Strength::new(HandCategory::Straight, &[high]);
+    }
+
+    if let Some(&three) = trips.first() {
+        return Strength::new(HandCategory::ThreeOfAKind, &[&[three][..], &kickers(&[three], 2)].concat());
+    }
+
+    match pairs.as_slice() {
+        [high, low, ..] => Strength::new(HandCategory::TwoPair, &[*high, *low, kickers(&[*high, *low], 1)[0]]),
+        [pair] => Strength::new(HandCategory::Pair, &[&[*pair][..], &kickers(&[*pair], 3)].concat()),
+        [] => Strength::new(HandCategory::HighCard, &kickers(&[], 5)),
+    }
+}
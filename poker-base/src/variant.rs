@@ -0,0 +1,80 @@
+//! The video poker games that can be computed.
+
+use core::fmt;
+use std::str::FromStr;
+
+use serde::{Serialize, Deserialize};
+
+use crate::{game::{self, GameVariant}, Card, Evaluation, Paytable};
+
+/// The video poker games known to the farm, identifying the game of a computation block or state.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash, Default, Serialize, Deserialize)]
+pub enum Variant {
+    #[default]
+    JacksOrBetter,
+    /// All deuces are wild.
+    DeucesWild,
+    /// Played with a 53-card deck, the joker being wild.
+    JokerPoker,
+}
+
+impl Variant {
+    pub const ALL: [Variant; 3] = [Variant::JacksOrBetter, Variant::DeucesWild, Variant::JokerPoker];
+
+    /// The rules of the game.
+    pub fn game(&self) -> &'static dyn GameVariant {
+        match self {
+            Variant::JacksOrBetter => &game::JacksOrBetter,
+            Variant::DeucesWild => &game::DeucesWild,
+            Variant::JokerPoker => &game::JokerPoker,
+        }
+    }
+}
+
+impl GameVariant for Variant {
+    fn name(&self) -> &'static str {
+        self.game().name()
+    }
+
+    fn deck(&self) -> Vec<Card> {
+        self.game().deck()
+    }
+
+    fn hand_size(&self) -> usize {
+        self.game().hand_size()
+    }
+
+    fn is_wild(&self, card: Card) -> bool {
+        self.game().is_wild(card)
+    }
+
+    fn evaluate(&self, cards: Vec<Card>) -> Evaluation {
+        self.game().evaluate(cards)
+    }
+
+    fn paytable(&self) -> Paytable {
+        self.game().paytable()
+    }
+
+    fn paytables(&self) -> Vec<Paytable> {
+        self.game().paytables()
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.name())
+    }
+}
+
+impl FromStr for Variant {
+    type Err = String;
+
+    /// Parses a variant by its [name](GameVariant::name) (e.g. `jacks-or-better`) or its serialized name (e.g. `JacksOrBetter`).
+    fn from_str(variant: &str) -> Result<Self, Self::Err> {
+        Variant::ALL
+            .into_iter()
+            .find(|candidate| candidate.name() == variant || format!("{candidate:?}") == variant)
+            .ok_or_else(|| format!("Unknown variant `{variant}`."))
+    }
+}
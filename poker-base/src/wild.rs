@@ -0,0 +1,98 @@
+//! Evaluation of hands containing wild cards.
+
+use crate::{compute_rank, Card, Evaluation, Rank, StraightFlushDetails, Suit, Value};
+
+/// Computes the best rank the given cards can form, with each wild card standing in for any card.
+/// # Panics
+/// if cards not len of 5 or a joker is given that is not wild.
+pub fn compute_wild_rank(cards: Vec<Card>, is_wild: impl Fn(Card) -> bool) -> Evaluation {
+    assert_eq!(cards.len(), 5, "cards must be of length 5");
+
+    let (wild, natural): (Vec<Card>, Vec<Card>) = cards.into_iter().partition(|&card| is_wild(card));
+
+    assert!(natural.iter().all(|card| !card.is_joker()), "jokers cannot be evaluated unless wild");
+
+    if wild.is_empty() {
+        return compute_rank(natural).into();
+    }
+
+    Evaluation { rank: compute_best_rank(&natural, wild.len()), wilds: wild.len() }
+}
+
+/// The highest value of which (including the wild cards) at least `amount` cards are available.
+fn best_kind(counts: &[usize; 13], wilds: usize, amount: usize) -> Option<Value> {
+    Value::ALL.iter().rev().copied().find(|&value| counts[value as usize] + wilds >= amount)
+}
+
+/// The highest card of the best straight the natural cards can be completed to.
+fn best_straight(natural: &[Card]) -> Option<Value> {
+    // try the highest straights first; the wheel (five high) is the only one containing a low ace.
+    Value::ALL[3..].iter().rev().copied().find(|&high| {
+        natural.iter().all(|card| {
+            let value = card.value as usize;
+
+            if high == Value::Five {
+                value <= Value::Five as usize || card.value == Value::Ace
+            } else {
+                value <= high as usize && value + 4 >= high as usize
+            }
+        })
+    })
+}
+
+fn compute_best_rank(natural: &[Card], wilds: usize) -> Rank {
+    let mut counts = [0usize; 13];
+    for card in natural {
+        counts[card.value as usize] += 1;
+    }
+
+    let distinct = counts.iter().all(|&count| count <= 1);
+    let suited = natural.windows(2).all(|cards| cards[0].suit == cards[1].suit);
+    let straight = if distinct { best_straight(natural) } else { None };
+
+    // the suit of the highest natural card.
+    let suit = natural.iter().max().map(|card| card.suit).unwrap_or(Suit::Spade);
+
+    if let Some(value) = best_kind(&counts, wilds, 5) {
+        return Rank::FiveOfAKind(value);
+    }
+
+    if let (true, Some(high)) = (suited, straight) {
+        if high == Value::Ace {
+            return Rank::RoyalFlush(suit);
+        }
+
+        return Rank::StraightFlush(StraightFlushDetails { high, suit });
+    }
+
+    if let Some(value) = best_kind(&counts, wilds, 4) {
+        // without five of a kind, the fifth card is always a natural one of another value.
+        let kicker = natural.iter().find(|card| card.value != value).unwrap().value;
+
+        return Rank::FourOfAKind { value, kicker };
+    }
+
+    let pairs: Vec<Value> = Value::ALL.iter().rev().copied().filter(|&value| counts[value as usize] == 2).collect();
+
+    if let (1, [three_of_a_kind, pair]) = (wilds, pairs.as_slice()) {
+        return Rank::FullHouse { three_of_a_kind: *three_of_a_kind, pair: *pair };
+    }
+
+    if suited {
+        // the wild cards stand in for the highest missing cards of the suit.
+        let high = Value::ALL.iter().rev().copied().find(|&value| counts[value as usize] == 0).unwrap();
+
+        return Rank::Flush(high.max(natural.iter().max().unwrap().value));
+    }
+
+    if let Some(high) = straight {
+        return Rank::Straight { high, suit };
+    }
+
+    if let Some(value) = best_kind(&counts, wilds, 3) {
+        return Rank::ThreeOfAKind(value);
+    }
+
+    // a single wild card without any pair.
+    Rank::Pair(natural.iter().max().unwrap().value)
+}
@@ -0,0 +1,58 @@
+use poker_base::{equity::{self, EquityError, Method}, parse_cards, Card};
+
+fn hand(cards: &str) -> [Card; 2] {
+    parse_cards(cards).unwrap().try_into().unwrap()
+}
+
+/// Aces against kings without shared suits: 81.26% equity.
+#[test]
+fn aces_against_kings_preflop() {
+    let equity = equity::calculate(&[hand("Ac Ad"), hand("Kh Ks")], &[], &[], Method::Exact).unwrap();
+
+    assert!(equity.exact);
+    assert_eq!(equity.boards, 1_712_304);
+    assert!((equity.equity(0) - 0.8126).abs() < 1e-4, "equity was {}", equity.equity(0));
+    assert!((equity.equity(0) + equity.equity(1) - 1f64).abs() < 1e-9);
+}
+
+#[test]
+fn complete_board_is_decided() {
+    let board = parse_cards("2h 7d 9c Js 3s").unwrap();
+    let equity = equity::calculate(&[hand("Ah Kh"), hand("9h 9d"), hand("As Kd")], &board, &[], Method::Auto).unwrap();
+
+    assert_eq!(equity.boards, 1);
+    assert_eq!(equity.win(1), 1f64);
+    assert_eq!(equity.tie(0), 0f64);
+    assert_eq!(equity.lose(2), 1f64);
+}
+
+#[test]
+fn split_pot_is_shared() {
+    // the board plays for both hands.
+    let board = parse_cards("Ts Js Qs Ks As").unwrap();
+    let equity = equity::calculate(&[hand("2c 3c"), hand("4d 5d")], &board, &[], Method::Exact).unwrap();
+
+    assert_eq!(equity.tie(0), 1f64);
+    assert_eq!(equity.equity(1), 0.5);
+}
+
+#[test]
+fn monte_carlo_agrees_with_enumeration() {
+    let hands = [hand("Ah Kh"), hand("Qc Qd")];
+    let board = parse_cards("Th 4h").unwrap();
+    let dead = parse_cards("2c").unwrap();
+
+    let exact = equity::calculate(&hands, &board, &dead, Method::Exact).unwrap();
+    let sampled = equity::calculate(&hands, &board, &dead, Method::MonteCarlo { trials: 200_000, seed: 7 }).unwrap();
+
+    assert!(!sampled.exact);
+    assert!((exact.equity(0) - sampled.equity(0)).abs() < 0.005);
+    assert_eq!(sampled, equity::calculate(&hands, &board, &dead, Method::MonteCarlo { trials: 200_000, seed: 7 }).unwrap());
+}
+
+#[test]
+fn duplicate_cards_are_rejected() {
+    let error = equity::calculate(&[hand("Ah Kh"), hand("Ah Qd")], &[], &[], Method::Auto).unwrap_err();
+
+    assert_eq!(error, EquityError::DuplicateCard(hand("Ah Kh")[0]));
+}
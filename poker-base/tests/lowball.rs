@@ -0,0 +1,52 @@
+use poker_base::{compute_ace_to_five_rank, compute_deuce_to_seven_rank, parse_cards, parse_deck_cards, Deck, HandCategory, Lowball, Value};
+
+fn deuce_to_seven(cards: &str) -> poker_base::LowRank {
+    compute_deuce_to_seven_rank(parse_cards(cards).unwrap())
+}
+
+fn ace_to_five(cards: &str) -> poker_base::LowRank {
+    compute_ace_to_five_rank(parse_cards(cards).unwrap())
+}
+
+#[test]
+fn deuce_to_seven_ranks() {
+    let best = deuce_to_seven("7h 5c 4d 3s 2h");
+
+    // straights and flushes count against the hand.
+    assert!(best > deuce_to_seven("8h 5c 4d 3s 2h"));
+    assert!(best > deuce_to_seven("7h 5h 4h 3h 2h"));
+    assert_eq!(deuce_to_seven("6h 5c 4d 3s 2h").category(), HandCategory::Straight);
+    assert!(deuce_to_seven("8h 6c 4d 3s 2h") > deuce_to_seven("8h 6c 5d 3s 2h"));
+
+    // the ace is high only: A-2-3-4-5 is an ace high hand, worse than any king high hand.
+    let wheel = deuce_to_seven("Ah 2c 3d 4s 5h");
+    assert_eq!(wheel.category(), HandCategory::HighCard);
+    assert_eq!(wheel.values()[0], Value::Ace);
+    assert!(deuce_to_seven("Kh Qc Jd Ts 8h") > wheel);
+    assert!(wheel > deuce_to_seven("2h 2c 3d 4s 5h"));
+}
+
+#[test]
+fn ace_to_five_ranks() {
+    let wheel = ace_to_five("Ah 2h 3h 4h 5h");
+
+    // the ace is low and straights and flushes do not count.
+    assert_eq!(wheel.category(), HandCategory::HighCard);
+    assert!(wheel > ace_to_five("6h 4c 3d 2s Ah"));
+    assert!(ace_to_five("6h 4c 3d 2s Ah") > ace_to_five("6h 5c 3d 2s Ah"));
+    assert!(ace_to_five("Kh Qc Jd Ts 9h") > ace_to_five("Ah Ac 2d 3s 4h"));
+    assert!(ace_to_five("Ah Ac 2d 3s 4h") > ace_to_five("2h 2c Ad 3s 4h"));
+    assert_eq!(ace_to_five("2h 2c 2d As Ah").category(), HandCategory::FullHouse);
+}
+
+#[test]
+#[should_panic(expected = "jokers cannot be evaluated")]
+fn ace_to_five_rejects_jokers() {
+    Lowball::AceToFive.evaluate(parse_deck_cards("Ah 2c 3d 4s X", &Deck::with_joker().cards).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "jokers cannot be evaluated")]
+fn deuce_to_seven_rejects_jokers() {
+    Lowball::DeuceToSeven.evaluate(parse_deck_cards("7h 5c 4d 3s X", &Deck::with_joker().cards).unwrap());
+}
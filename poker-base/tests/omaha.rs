@@ -0,0 +1,62 @@
+use poker_base::{equity::Method, omaha::{self, Low}, parse_cards, Card, HandCategory, Value};
+
+fn cards<const N: usize>(cards: &str) -> [Card; N] {
+    parse_cards(cards).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn exactly_two_hole_cards_are_played() {
+    // four hearts on the board and a single heart in the hand is no flush, and three aces in the hand only a pair.
+    let board = cards("2h 7h 9h Jh Kc");
+
+    assert_eq!(omaha::best_high(&cards("Ah Ac Ad 3s"), &board).category, HandCategory::Pair);
+    assert_eq!(omaha::best_high(&cards("Ah Qh 4c 4d"), &board).category, HandCategory::Flush);
+}
+
+#[test]
+fn three_board_cards_are_played() {
+    // quads on the board only make a full house at best.
+    let board = cards("8c 8d 8h 8s 2c");
+
+    assert_eq!(omaha::best_high(&cards("Ac Ad 3s 4s"), &board).category, HandCategory::FullHouse);
+}
+
+#[test]
+fn lows_qualify_with_eight_or_better() {
+    assert_eq!(Low::new(&cards("Ah 2c 3d 4s 5h")).unwrap().values(), [Value::Five, Value::Four, Value::Three, Value::Two, Value::Ace]);
+    assert!(Low::new(&cards("Ah 2c 3d 4s 9h")).is_none());
+    assert!(Low::new(&cards("Ah 2c 3d 3s 5h")).is_none());
+    assert!(Low::new(&cards("Ah 2c 3d 4s 6h")) < Low::new(&cards("Ah 2c 3d 4s 5h")));
+    assert!(Low::new(&cards("8h 2c 3d 4s 5h")) < Low::new(&cards("7h 6c 5d 4s 3h")));
+
+    let board = cards("3c 5d 7h Kc Qd");
+
+    assert_eq!(omaha::best_low(&cards("Ah 2h Kd Ks"), &board).unwrap().to_string(), "7532A");
+    assert!(omaha::best_low(&cards("Kh Qh Jd Ts"), &board).is_none());
+}
+
+#[test]
+fn hi_lo_splits_the_pot() {
+    // the first hand makes the nut low and the second the high on this river.
+    let board: [Card; 5] = cards("3c 5d 7h Kc Ks");
+    let hands: [[Card; 4]; 2] = [cards("Ah 2h 9d Ts"), cards("Kd Qh Jd Js")];
+
+    let equity = omaha::calculate_hi_lo(&hands, &board, &[], Method::Exact).unwrap();
+
+    assert_eq!((equity.boards, equity.low_boards), (1, 1));
+    assert_eq!(equity.equity(0), 0.5);
+    assert_eq!(equity.scoop(1), 0f64);
+}
+
+#[test]
+fn hi_lo_equity_sums_to_one() {
+    let board: [Card; 3] = cards("3c 5d Kh");
+    let hands: [[Card; 4]; 3] = [cards("Ah 2h 9d Ts"), cards("Kd Qh Jd Js"), cards("4c 6c 8s 8d")];
+
+    let equity = omaha::calculate_hi_lo(&hands, &board, &[], Method::Exact).unwrap();
+    let high = omaha::calculate(&hands, &board, &[], Method::Exact).unwrap();
+
+    assert!(equity.exact);
+    assert!(((0..3).map(|hand| equity.equity(hand)).sum::<f64>() - 1f64).abs() < 1e-9);
+    assert!((0..3).all(|hand| (equity.hands[hand].high.share - high.hands[hand].share).abs() < 1e-9));
+}
@@ -0,0 +1,121 @@
+use poker_base::{
+    compute_rank, game::{DeucesWild, JokerPoker}, parse_cards, parse_deck_cards, parse_deck_hand, parse_hand, range::Range, Card, Deck, GameVariant, ParseError, ParseErrorKind, Rank,
+    StraightFlushDetails, Suit, Value,
+};
+
+fn card(value: Value, suit: Suit) -> Card {
+    Card { suit, value }
+}
+
+#[test]
+fn ten_may_be_written_as_t_or_10() {
+    assert_eq!("Th".parse::<Card>().unwrap(), card(Value::Ten, Suit::Heart));
+    assert_eq!("10h".parse::<Card>().unwrap(), card(Value::Ten, Suit::Heart));
+    assert_eq!("10".parse::<Value>().unwrap(), Value::Ten);
+    assert_eq!(parse_cards("10♠ Ts").unwrap_err().kind, ParseErrorKind::DuplicateCard(card(Value::Ten, Suit::Spade)));
+}
+
+#[test]
+fn case_and_spacing_are_ignored() {
+    let expected = vec![card(Value::Ace, Suit::Heart), card(Value::King, Suit::Spade), card(Value::Queen, Suit::Club)];
+
+    assert_eq!(parse_cards("Ah Ks Qc").unwrap(), expected);
+    assert_eq!(parse_cards("ah ks qc").unwrap(), expected);
+    assert_eq!(parse_cards("AH,KS,QC").unwrap(), expected);
+    assert_eq!(parse_cards("  A h ,  K♠\tQ ♣ ").unwrap(), expected);
+    assert_eq!(parse_cards("").unwrap(), Vec::new());
+    assert_eq!(" d ".parse::<Suit>().unwrap(), Suit::Diamond);
+}
+
+#[test]
+fn errors_point_at_the_offending_character() {
+    assert_eq!(parse_cards("Ah Zh").unwrap_err(), ParseError { position: 3, kind: ParseErrorKind::InvalidValue('Z') });
+    assert_eq!(parse_cards("Ah Kz").unwrap_err(), ParseError { position: 4, kind: ParseErrorKind::InvalidSuit('z') });
+    assert_eq!(parse_cards("Ah 1h").unwrap_err(), ParseError { position: 3, kind: ParseErrorKind::InvalidValue('1') });
+    assert_eq!(parse_cards("Ah K").unwrap_err(), ParseError { position: 4, kind: ParseErrorKind::UnexpectedEnd });
+    assert_eq!("Ah Kh".parse::<Card>().unwrap_err(), ParseError { position: 3, kind: ParseErrorKind::UnexpectedCharacter('K') });
+    assert_eq!(parse_cards("Ah Zh").unwrap_err().to_string(), "invalid card value `Z` at position 3");
+}
+
+#[test]
+fn duplicates_are_rejected() {
+    assert_eq!(
+        parse_cards("Ah Kh ah").unwrap_err(),
+        ParseError { position: 6, kind: ParseErrorKind::DuplicateCard(card(Value::Ace, Suit::Heart)) }
+    );
+    assert!("Ah Kh Qh Jh ♥A".parse::<Deck<5>>().is_err());
+}
+
+#[test]
+fn decks_must_have_their_length() {
+    let hand: Deck<5> = "Ah Kh Qh Jh 10h".parse().unwrap();
+    assert_eq!(hand.to_string(), "A♥ K♥ Q♥ J♥ T♥");
+
+    assert_eq!(
+        "Ah Kh".parse::<Deck<5>>().unwrap_err(),
+        ParseError { position: 5, kind: ParseErrorKind::CardCount { expected: 5, found: 2 } }
+    );
+    assert_eq!(
+        "Ah Kh Qh Jh Th 9h".parse::<Deck<5>>().unwrap_err().kind,
+        ParseErrorKind::CardCount { expected: 5, found: 6 }
+    );
+
+    assert_eq!(parse_hand("Ah Kh Qh Jh 10h").unwrap(), hand.cards);
+    assert_eq!(parse_hand("Ah Kh").unwrap_err(), ParseError { position: 5, kind: ParseErrorKind::CardCount { expected: 5, found: 2 } });
+}
+
+#[test]
+fn ranks_round_trip() {
+    let ranks = [
+        Rank::HighCard(Value::Ace),
+        Rank::Pair(Value::Jack),
+        Rank::TwoPair { a: Value::King, b: Value::Seven },
+        Rank::ThreeOfAKind(Value::Two),
+        Rank::Straight { high: Value::Five, suit: Suit::Club },
+        Rank::Flush(Value::Queen),
+        Rank::FullHouse { three_of_a_kind: Value::Ten, pair: Value::Nine },
+        Rank::FourOfAKind { value: Value::Ace, kicker: Value::Three },
+        Rank::StraightFlush(StraightFlushDetails { high: Value::Nine, suit: Suit::Diamond }),
+        Rank::RoyalFlush(Suit::Spade),
+        Rank::FiveOfAKind(Value::Eight),
+    ];
+
+    for rank in ranks {
+        assert_eq!(rank.to_string().parse::<Rank>().unwrap(), rank);
+    }
+
+    assert_eq!("twopair( k , 7 )".parse::<Rank>().unwrap(), Rank::TwoPair { a: Value::King, b: Value::Seven });
+    assert_eq!("Quads(A)".parse::<Rank>().unwrap_err(), ParseError { position: 0, kind: ParseErrorKind::UnknownRank });
+    assert_eq!("Pair(J".parse::<Rank>().unwrap_err().kind, ParseErrorKind::UnexpectedEnd);
+}
+
+#[test]
+fn jokers_are_only_part_of_the_joker_deck() {
+    let not_in_deck = |position| ParseError { position, kind: ParseErrorKind::NotInDeck(Card::JOKER) };
+
+    assert_eq!(parse_cards("Ah X").unwrap_err(), not_in_deck(3));
+    assert_eq!("x".parse::<Card>().unwrap_err(), not_in_deck(0));
+    assert_eq!("Ah Kh Qh Jh 🃏".parse::<Deck<5>>().unwrap_err(), not_in_deck(12));
+    assert_eq!("AK, 🃏K".parse::<Range>().unwrap_err(), not_in_deck(4));
+    assert_eq!("AhX".parse::<Range>().unwrap_err(), not_in_deck(2));
+
+    let hand = parse_deck_cards("Ah X Kh, Qh Jh", &Deck::with_joker().cards).unwrap();
+    assert_eq!(hand[1], Card::JOKER);
+    assert_eq!(JokerPoker.evaluate(hand.clone()).rank, Rank::RoyalFlush(Suit::Heart));
+    assert_eq!(parse_deck_hand("Ah X Kh, Qh Jh", &JokerPoker.deck()).unwrap().to_vec(), hand);
+    assert_eq!(parse_hand("Ah X Kh, Qh Jh").unwrap_err(), not_in_deck(3));
+
+    assert_eq!(parse_deck_cards("X X", &JokerPoker.deck()).unwrap_err().kind, ParseErrorKind::DuplicateCard(Card::JOKER));
+}
+
+#[test]
+#[should_panic(expected = "jokers cannot be evaluated")]
+fn jokers_are_not_ranked_without_wild_cards() {
+    compute_rank(parse_deck_cards("Ah Kh Qh Jh X", &Deck::with_joker().cards).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "jokers cannot be evaluated unless wild")]
+fn jokers_are_not_ranked_by_other_wild_games() {
+    DeucesWild.evaluate(parse_deck_cards("2h Kh Qh Jh X", &Deck::with_joker().cards).unwrap());
+}
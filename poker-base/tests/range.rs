@@ -0,0 +1,86 @@
+use poker_base::{equity::{self, EquityError, Method}, parse_cards, range::{self, Range}, Card, ParseErrorKind};
+
+fn range(notation: &str) -> Range {
+    notation.parse().unwrap()
+}
+
+#[test]
+fn notation_expands_to_combos() {
+    assert_eq!(range("AKs").combos.len(), 4);
+    assert_eq!(range("KQo").combos.len(), 12);
+    assert_eq!(range("AK").combos.len(), 16);
+    assert_eq!(range("TT+").combos.len(), 5 * 6);
+    assert_eq!(range("TT-77").combos.len(), 4 * 6);
+    assert_eq!(range("A2s-A5s").combos.len(), 4 * 4);
+    assert_eq!(range("A2s+").combos.len(), 12 * 4);
+    assert_eq!(range("AhKh, Ah Kh").combos.len(), 1);
+    assert_eq!(range("22+, A2+, K2+, Q2+, J2+, T2+, 92+, 82+, 72+, 62+, 52+, 42+, 32").combos.len(), 1326);
+}
+
+#[test]
+fn weights_override_earlier_entries() {
+    let range = range("AK, AKo:0.5");
+
+    assert_eq!(range.combos.len(), 16);
+    assert_eq!(range.size(), 4f64 + 12f64 * 0.5);
+}
+
+#[test]
+fn invalid_notation_is_rejected() {
+    assert_eq!("AKs-QJs".parse::<Range>().unwrap_err().kind, ParseErrorKind::InvalidRange);
+    assert_eq!("AKs:2".parse::<Range>().unwrap_err().kind, ParseErrorKind::InvalidWeight);
+    assert_eq!("AZ".parse::<Range>().unwrap_err().kind, ParseErrorKind::InvalidValue('Z'));
+}
+
+#[test]
+fn known_cards_are_removed() {
+    let dead = parse_cards("Ah").unwrap();
+
+    assert_eq!(range("AA").without(&dead).combos.len(), 3);
+    assert_eq!(range("AKs").without(&dead).combos.len(), 3);
+}
+
+#[test]
+fn single_combos_match_hand_equity() {
+    let board = parse_cards("2c 7d 9h").unwrap();
+    let hands: [[Card; 2]; 2] = [parse_cards("Ac Ad").unwrap().try_into().unwrap(), parse_cards("Kh Ks").unwrap().try_into().unwrap()];
+
+    let hand = equity::calculate(&hands, &board, &[], Method::Exact).unwrap();
+    let ranges = range::calculate(&[range("AcAd"), range("KhKs")], &board, &[], Method::Exact).unwrap();
+
+    assert!((hand.equity(0) - ranges.ranges[0].equity).abs() < 1e-12);
+}
+
+#[test]
+fn monte_carlo_agrees_with_enumeration() {
+    let board = parse_cards("2c 7d 9h").unwrap();
+    let ranges = [range("AA, KK"), range("99, 77, AKs")];
+
+    let exact = range::calculate(&ranges, &board, &[], Method::Exact).unwrap();
+    let sampled = range::calculate(&ranges, &board, &[], Method::MonteCarlo { trials: 200_000, seed: 3 }).unwrap();
+
+    assert!(exact.exact && !sampled.exact);
+    assert!((exact.ranges[0].equity - sampled.ranges[0].equity).abs() < 0.005);
+    assert!((exact.ranges[0].equity + exact.ranges[1].equity - 1f64).abs() < 1e-9);
+}
+
+#[test]
+fn impossible_ranges_are_rejected() {
+    let board = parse_cards("Ac Ad Ah").unwrap();
+
+    let error = range::calculate(&[range("AA"), range("KK")], &board, &[], Method::Auto).unwrap_err();
+    assert_eq!(error, EquityError::EmptyRange(0));
+
+    let error = range::calculate(&[range("AhKh"), range("AhQh")], &[], &[], Method::Auto).unwrap_err();
+    assert_eq!(error, EquityError::ConflictingRanges);
+}
+
+#[test]
+fn uncountable_deals_are_sampled() {
+    let any = range("22+, A2+, K2+, Q2+, J2+, T2+, 92+, 82+, 72+, 62+, 52+, 42+, 32");
+
+    let result = range::calculate(&[any.clone(), any.clone(), any.clone(), any.clone(), any], &[], &[], Method::Auto).unwrap();
+
+    assert!(!result.exact);
+    assert!((result.ranges.iter().map(|range| range.equity).sum::<f64>() - 1f64).abs() < 1e-9);
+}
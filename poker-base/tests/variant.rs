@@ -0,0 +1,51 @@
+use poker_base::{game, parse_cards, Card, GameVariant, Paytable, Rank, Suit, Value, Variant};
+
+/// Every variant dispatches to the rules of its game, both directly and as a trait object.
+#[test]
+fn variants_dispatch_to_their_game() {
+    let hand = parse_cards("Ah Kh Qh Jh 2h").unwrap();
+
+    let expected = [
+        (Variant::JacksOrBetter, "jacks-or-better", 52, 0, Rank::Flush(Value::Ace)),
+        (Variant::DeucesWild, "deuces-wild", 52, 4, Rank::RoyalFlush(Suit::Heart)),
+        (Variant::JokerPoker, "joker-poker", 53, 1, Rank::Flush(Value::Ace)),
+    ];
+
+    assert_eq!(Variant::ALL.len(), expected.len());
+
+    for (variant, (expected_variant, name, deck_size, wilds, rank)) in Variant::ALL.into_iter().zip(expected) {
+        assert_eq!(variant, expected_variant);
+
+        let game: &dyn GameVariant = &variant;
+
+        assert_eq!(game.name(), name);
+        assert_eq!(game.name(), variant.game().name());
+        assert_eq!(variant.to_string().parse::<Variant>().unwrap(), variant);
+        assert_eq!(serde_json::to_value(variant).unwrap().as_str().unwrap().parse::<Variant>().unwrap(), variant);
+
+        assert_eq!(game.deck(), variant.game().deck());
+        assert_eq!(game.deck().len(), deck_size);
+        assert_eq!(game.hand_size(), variant.game().hand_size());
+        assert_eq!(game.hand_size(), 5);
+        assert_eq!(game.deck().into_iter().filter(|&card| game.is_wild(card)).count(), wilds);
+        assert_eq!(game.deck().contains(&Card::JOKER), variant == Variant::JokerPoker);
+
+        assert_eq!(game.evaluate(hand.clone()).rank, rank);
+        assert_eq!(game.evaluate(hand.clone()), variant.game().evaluate(hand.clone()));
+
+        assert_eq!(game.paytable(), variant.game().paytable());
+        assert!(game.paytables().contains(&game.paytable()));
+        assert!(game.paytable().score(game.evaluate(hand.clone())) > 0);
+    }
+}
+
+/// Games are named by a variant, a paytable preset or both; a paytable made for another variant is rejected.
+#[test]
+fn games_resolve_to_matching_variants_and_paytables() {
+    assert_eq!(game::resolve("deuces-wild").unwrap(), (Variant::DeucesWild, Paytable::deuces_wild_full_pay()));
+    assert_eq!(game::resolve("deuces-wild-full-pay").unwrap(), (Variant::DeucesWild, Paytable::deuces_wild_full_pay()));
+    assert_eq!(game::resolve("jacks-or-better:bonus-poker-8-5").unwrap(), (Variant::JacksOrBetter, Paytable::bonus_poker_8_5()));
+
+    assert!(game::resolve("jacks-or-better:deuces-wild-full-pay").is_err());
+    assert!(game::resolve("joker-poker:jacks-or-better-9-6").is_err());
+}
@@ -1,149 +1,10 @@
-use std::{env, error::Error, io::Write, net::TcpStream, sync::Arc, thread, time::Instant};
+use std::{env, error::Error, io::Write, net::TcpStream, sync::Arc, thread, time::{Duration, Instant}};
 
-use itertools::Itertools;
-use poker_base::{Card, ComputationBlock, ComputedBlock, ComputedMove, Rank, StraightFlushDetails, Value};
+use poker_base::{ComputationBlock, ComputedBlock, GameVariant};
+use poker_strategy::video_poker::compute_combinations;
 
-/// Calculates the score of the given [EvalClass] in accordance with [win2day](https://www.win2day.at/fairplay/spielbedingungen/jacksorbetter-spielbedingungen).
-const fn calculate_score(class: Rank) -> usize {
-    match class {
-        Rank::Pair(Value::Jack) |
-        Rank::Pair(Value::Queen) |
-        Rank::Pair(Value::King) |
-        Rank::Pair(Value::Ace) => 1,
-
-        Rank::TwoPair { .. } => 2,
-        Rank::ThreeOfAKind { .. } => 3,
-        Rank::Straight { .. } => 4,
-        Rank::Flush { .. } => 6,
-        Rank::FullHouse { .. } => 9,
-        Rank::FourOfAKind { .. } => 25,
-
-        Rank::StraightFlush(StraightFlushDetails { high: Value::Ace, suit: _ }) => 250, // royal
-
-        Rank::StraightFlush { .. } => 50,
-
-        _ => 0
-    }
-}
-
-/// Calculates the average score when keeping the given cards.
-fn calculate_avg_score(kept: &[Card], remaining: &[Card]) -> f64 {
-    let mut total = 0usize;
-
-    let combinations = remaining
-        .iter()
-        .copied()
-        .combinations(5 - kept.len());
-
-    let amount = combinations.size_hint().0;
-    
-    for remaining in combinations {
-        let hand = kept
-            .iter()
-            .copied()
-            .chain(remaining.into_iter())
-            .collect::<Vec<_>>();
-        
-        let score = calculate_score(poker_base::compute_rank(hand));
-
-        total += score;
-    }
-
-    (total as f64) / (amount as f64)
-}
-
-fn calculate_optimal(remaining: &[Card], shown: &[Card; 5]) -> ComputedMove {
-    let mut max_score = 0f64;
-    let mut optimal = Vec::default();
-
-    for keep in 0..=shown.len() {
-        let kept_combinations = shown
-            .iter()
-            .copied()
-            .combinations(keep);
-        
-        for kept in kept_combinations {
-            let score = calculate_avg_score(&kept, remaining);
-            
-            if score > max_score {
-                max_score = score;
-                optimal = kept.iter().map(|card| shown.iter().position(|shown| shown == card).unwrap()).collect();
-            }
-        }
-    }
-
-    ComputedMove {
-        pattern: *shown,
-        keep: optimal,
-        average_score: max_score
-    }
-}
-
-fn compute_combinations(mut combinations: Vec<[Card; 5]>) -> ComputedBlock {
-    let deck = Arc::new(Card::full_deck());
-    let mut moves: Vec<_> = Vec::with_capacity(combinations.len()); // shown: chosen
-
-    let cpus = thread::available_parallelism().map(|parallelism| parallelism.get()).unwrap_or(1);
-    let combinations_per_cpu = combinations.len() / cpus;
-
-    let mut handles = Vec::with_capacity(cpus);
-
-    for _ in 0..cpus {
-        let thread_combinations: Vec<_> = combinations.drain(0..combinations_per_cpu).collect();
-        let deck = deck.clone();
-
-        // TODO: Spwaning threads is expensive. potentially spawn at start and then alwys move load to them.
-        handles.push(thread::spawn(move || {
-            let mut moves: Vec<_> = Vec::with_capacity(thread_combinations.len());
-
-            for shown in thread_combinations {
-                let remaining = deck
-                    .iter()
-                    .filter(|card| !shown.contains(card))
-                    .copied()
-                    .collect::<Vec<_>>();
-                
-                let optimal = calculate_optimal(&remaining, &shown);
-                
-                moves.push(optimal);
-            }
-
-            moves
-        }));
-    }
-
-    for shown in combinations {
-        let remaining = deck
-            .iter()
-            .filter(|card| !shown.contains(card))
-            .copied()
-            .collect::<Vec<_>>();
-        
-        let optimal = calculate_optimal(&remaining, &shown);
-        
-        moves.push(optimal);
-    }
-
-    for handle in handles {
-        moves.extend(handle.join().unwrap());
-    }
-    
-    // for shown in combinations {
-    //     let remaining = deck
-    //             .iter()
-    //             .filter(|card| !shown.contains(card))
-    //             .copied()
-    //             .collect::<Vec<_>>();
-        
-    //     let optimal = calculate_optimal(&remaining, shown);
-        
-    //     moves.push(optimal);
-
-    //     log::info!("{}/{} ({}%)", moves.len(), combinations.len(), ((moves.len() as f64) / (combinations.len() as f64)) * 100f64);
-    // }
-
-    ComputedBlock { moves }
-}
+/// How long to wait before asking again when the server has no work left.
+pub const IDLE_BACKOFF: Duration = Duration::from_secs(60);
 
 fn start(peer: String) -> Result<(), Box<dyn Error>> {    
     log::info!("Starting compute loop.");
@@ -157,15 +18,26 @@ fn start(peer: String) -> Result<(), Box<dyn Error>> {
 
         client.flush()?;
 
-        let block: ComputationBlock = serde_json::from_reader(&mut client)?;
+        // the server closes the connection without a block when there is no work left.
+        let block: ComputationBlock = match serde_json::from_reader(&mut client) {
+            Ok(block) => block,
+            Err(error) if error.is_eof() => {
+                log::info!("The server has no work left, asking again in {}s.", IDLE_BACKOFF.as_secs());
+
+                thread::sleep(IDLE_BACKOFF);
+
+                continue;
+            },
+            Err(error) => return Err(error.into()),
+        };
 
         drop(client);
 
-        log::info!("Received computation block of size {}: Starting computation...", block.patterns.len());
+        log::info!("Received computation block of size {} for job `{}` (`{}`): Starting computation...", block.patterns.len(), block.job, block.variant);
 
         let start = Instant::now();
 
-        let computed = compute_combinations(block.patterns);
+        let computed = compute(block.variant, block)?;
 
         log::info!("Computed block in {}ms.", start.elapsed().as_millis());
 
@@ -183,6 +55,20 @@ fn start(peer: String) -> Result<(), Box<dyn Error>> {
     }
 }
 
+/// Computes the patterns of a block of the given game, with the paytable of the block or else the default one of the game.
+fn compute<G: GameVariant + Copy + 'static>(game: G, block: ComputationBlock) -> Result<ComputedBlock, Box<dyn Error>> {
+    if game.hand_size() != 5 {
+        return Err(format!("`{}` deals {} cards, but blocks carry five card patterns.", game.name(), game.hand_size()).into());
+    }
+
+    let paytable = Arc::new(block.paytable.unwrap_or_else(|| game.paytable()));
+
+    let mut computed = compute_combinations(game, &paytable, block.patterns);
+    computed.job = block.job;
+
+    Ok(computed)
+}
+
 fn main() {
     if let Err(error) = simple_logger::SimpleLogger::new().env().init() {
         eprintln!("Logger initialization failed: {}", error);
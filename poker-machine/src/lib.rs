@@ -0,0 +1,5 @@
+//! A local video poker machine for testing bots and trainers end-to-end.
+
+pub mod machine;
+pub mod protocol;
+pub mod server;
@@ -0,0 +1,149 @@
+//! A single video poker machine: bets credits, deals, draws and pays by its paytable.
+
+use core::fmt;
+use std::error::Error;
+
+use poker_base::{Card, Category, GameVariant, Paytable, Payout, Variant};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+/// The most coins that can be bet on a hand.
+pub const MAX_BET: usize = 5;
+/// The most a natural royal flush pays per coin when betting less than [MAX_BET]. Like real machines, the
+/// paytables list the max-bet payout (e.g. 800), so a short bet forgoes the royal flush bonus.
+pub const SHORT_BET_ROYAL_PAYOUT: usize = 250;
+
+/// An action the machine does not allow in its current state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MachineError {
+    /// The bet is not between one coin and [MAX_BET].
+    InvalidBet(usize),
+    InsufficientCredits { bet: usize, credits: u64 },
+    /// A hand has been dealt and must be drawn first.
+    HandInPlay,
+    /// No hand has been dealt yet.
+    NoHand,
+    /// The index of a held card is not a position of the hand.
+    InvalidHold(usize),
+}
+
+impl fmt::Display for MachineError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MachineError::InvalidBet(bet) => write!(formatter, "invalid bet of {bet} coins (expected 1 to {MAX_BET})"),
+            MachineError::InsufficientCredits { bet, credits } => write!(formatter, "cannot bet {bet} coins with {credits} credits"),
+            MachineError::HandInPlay => write!(formatter, "the dealt hand must be drawn first"),
+            MachineError::NoHand => write!(formatter, "no hand has been dealt"),
+            MachineError::InvalidHold(index) => write!(formatter, "cannot hold card {index} of a hand of five"),
+        }
+    }
+}
+
+impl Error for MachineError {}
+
+/// A dealt hand waiting for the draw.
+#[derive(Debug, Clone)]
+struct Deal {
+    bet: usize,
+    /// The shuffled deck, the first five cards being the dealt hand and the next ones the replacements.
+    deck: Vec<Card>,
+}
+
+/// The result of a draw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draw {
+    pub hand: [Card; 5],
+    /// The index (into [Paytable::payouts]) of the category the hand is paid by, if any.
+    pub category: Option<usize>,
+    /// The credits won, i.e. the [Machine::payout] of the category times the bet.
+    pub win: u64,
+    pub credits: u64,
+}
+
+/// A machine playing a variant by a paytable with a seedable random number generator, so sessions can be replayed.
+#[derive(Debug, Clone)]
+pub struct Machine {
+    variant: Variant,
+    paytable: Paytable,
+    rng: StdRng,
+    credits: u64,
+    deal: Option<Deal>,
+}
+
+impl Machine {
+    pub fn new(variant: Variant, paytable: Paytable, credits: u64, seed: u64) -> Self {
+        Self { variant, paytable, rng: StdRng::seed_from_u64(seed), credits, deal: None }
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    pub fn paytable(&self) -> &Paytable {
+        &self.paytable
+    }
+
+    pub fn credits(&self) -> u64 {
+        self.credits
+    }
+
+    /// The payout per coin of the category (an index into [Paytable::payouts]) at the given bet.
+    pub fn payout(&self, category: usize, bet: usize) -> usize {
+        let Payout { category, payout } = self.paytable.payouts[category];
+
+        match category == Category::RoyalFlush && bet < MAX_BET {
+            true => payout.min(SHORT_BET_ROYAL_PAYOUT),
+            false => payout,
+        }
+    }
+
+    /// The dealt hand waiting for the draw, if any.
+    pub fn hand(&self) -> Option<[Card; 5]> {
+        self.deal.as_ref().map(|deal| deal.deck[..5].try_into().unwrap())
+    }
+
+    /// Bets the given amount of coins and deals a hand from a freshly shuffled deck.
+    pub fn deal(&mut self, bet: usize) -> Result<[Card; 5], MachineError> {
+        if self.deal.is_some() {
+            return Err(MachineError::HandInPlay);
+        }
+
+        if !(1..=MAX_BET).contains(&bet) {
+            return Err(MachineError::InvalidBet(bet));
+        }
+
+        if self.credits < bet as u64 {
+            return Err(MachineError::InsufficientCredits { bet, credits: self.credits });
+        }
+
+        let mut deck = self.variant.deck();
+        deck.shuffle(&mut self.rng);
+
+        self.credits -= bet as u64;
+        self.deal = Some(Deal { bet, deck });
+
+        Ok(self.hand().unwrap())
+    }
+
+    /// Keeps the cards at the given indices of the dealt hand, replaces the others and pays the final hand.
+    pub fn draw(&mut self, hold: &[usize]) -> Result<Draw, MachineError> {
+        let deal = self.deal.as_ref().ok_or(MachineError::NoHand)?;
+
+        if let Some(&index) = hold.iter().find(|&&index| index >= 5) {
+            return Err(MachineError::InvalidHold(index));
+        }
+
+        let mut replacements = deal.deck[5..].iter();
+        let hand: [Card; 5] = std::array::from_fn(|index| match hold.contains(&index) {
+            true => deal.deck[index],
+            false => *replacements.next().unwrap(),
+        });
+
+        let category = self.paytable.category(self.variant.evaluate(hand.to_vec()));
+        let win = category.map_or(0, |category| self.payout(category, deal.bet) * deal.bet) as u64;
+
+        self.credits += win;
+        self.deal = None;
+
+        Ok(Draw { hand, category, win, credits: self.credits })
+    }
+}
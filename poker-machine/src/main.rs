@@ -0,0 +1,53 @@
+use std::{env, error::Error, net::TcpListener};
+
+use poker_base::game;
+use poker_machine::server;
+
+/// The port listened on (on the loopback interface) unless another one is given.
+pub const DEFAULT_PORT: u16 = 5577;
+/// The credits each session starts with.
+pub const DEFAULT_CREDITS: u64 = 1000;
+
+fn run(game_name: &str, port: Option<&str>, seed: Option<&str>, credits: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let (variant, paytable) = game::resolve(game_name)?;
+    let port = port.map(str::parse).transpose()?.unwrap_or(DEFAULT_PORT);
+    let seed = seed.map(str::parse).transpose()?.unwrap_or_else(rand::random);
+    let credits = credits.map(str::parse).transpose()?.unwrap_or(DEFAULT_CREDITS);
+
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+
+    log::info!("Starting machine `{variant}` with paytable `{}` on `{}`...", paytable.name, listener.local_addr()?);
+
+    Ok(server::serve(listener, variant, paytable, seed, credits, server::SESSION_TIMEOUT)?)
+}
+
+fn main() {
+    if let Err(error) = simple_logger::SimpleLogger::new().env().init() {
+        eprintln!("Logger initialization failed: {}", error);
+
+        std::process::exit(1);
+    }
+
+    let args: Vec<String> = env::args().collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        [_, game] => run(game, None, None, None),
+        [_, game, port] => run(game, Some(port), None, None),
+        [_, game, port, seed] => run(game, Some(port), Some(seed), None),
+        [_, game, port, seed, credits] => run(game, Some(port), Some(seed), Some(credits)),
+        [binary, ..] => {
+            log::error!("Usage: {binary} <game> [port (default {DEFAULT_PORT}, 0 for any free port)] [seed] [credits (default {DEFAULT_CREDITS})]");
+            log::error!("A game is a variant, a paytable preset or `<variant>:<paytable (preset or JSON file)>`.");
+
+            std::process::exit(1);
+        },
+        [] => unreachable!(),
+    };
+
+    if let Err(error) = result {
+        log::error!("Fatal: {}", error);
+
+        std::process::exit(1);
+    }
+}
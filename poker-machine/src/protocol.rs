@@ -0,0 +1,77 @@
+//! The network protocol: every request and response is a JSON object on a line of its own, e.g.
+//!
+//! ```text
+//! > {"action":"deal","bet":5}
+//! < {"result":"dealt","hand":[...],"credits":995}
+//! > {"action":"draw","hold":[0,1]}
+//! < {"result":"drawn","hand":[...],"category":"Jacks or Better","win":5,"credits":1000}
+//! ```
+
+use std::io::{self, BufRead, Write};
+
+use poker_base::{Card, Paytable};
+use serde::{Serialize, Deserialize};
+
+use crate::machine::Machine;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Request {
+    /// Bets the given amount of coins and deals a hand.
+    Deal { bet: usize },
+    /// Holds the cards at the given indices of the dealt hand and draws.
+    Draw { hold: Vec<usize> },
+    Balance,
+    /// The paytable, listing the payouts at the max bet.
+    Paytable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum Response {
+    Dealt { hand: [Card; 5], credits: u64 },
+    /// The final hand, the name of the category it is paid by, and the credits won.
+    Drawn { hand: [Card; 5], category: Option<String>, win: u64, credits: u64 },
+    Balance { credits: u64 },
+    Paytable { paytable: Paytable },
+    Error { message: String },
+}
+
+/// Performs the request on the machine.
+pub fn respond(machine: &mut Machine, request: Request) -> Response {
+    let result = match request {
+        Request::Deal { bet } => machine.deal(bet).map(|hand| Response::Dealt { hand, credits: machine.credits() }),
+        Request::Draw { hold } => machine.draw(&hold).map(|draw| Response::Drawn {
+            hand: draw.hand,
+            category: draw.category.map(|category| machine.paytable().payouts[category].category.to_string()),
+            win: draw.win,
+            credits: draw.credits,
+        }),
+        Request::Balance => Ok(Response::Balance { credits: machine.credits() }),
+        Request::Paytable => Ok(Response::Paytable { paytable: machine.paytable().clone() }),
+    };
+
+    result.unwrap_or_else(|error| Response::Error { message: error.to_string() })
+}
+
+/// Answers the requests read line by line until the input ends. Malformed requests are answered with an error.
+pub fn serve(machine: &mut Machine, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str(&line) {
+            Ok(request) => respond(machine, request),
+            Err(error) => Response::Error { message: format!("invalid request: {error}") },
+        };
+
+        serde_json::to_writer(&mut output, &response)?;
+        writeln!(output)?;
+        output.flush()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+//! The TCP server hosting a machine for every connection.
+
+use std::{error::Error, io::{self, BufReader}, net::{TcpListener, TcpStream}, thread, time::Duration};
+
+use poker_base::{Paytable, Variant};
+
+use crate::{machine::Machine, protocol};
+
+/// How long a session may wait for the next request before it is closed.
+pub const SESSION_TIMEOUT: Duration = Duration::from_secs(600);
+
+fn handle_connection(mut machine: Machine, connection: TcpStream) -> Result<(), Box<dyn Error>> {
+    let peer = connection.peer_addr()?;
+
+    protocol::serve(&mut machine, BufReader::new(connection.try_clone()?), connection)?;
+
+    log::info!("Session of `{peer}` ended with {} credits.", machine.credits());
+
+    Ok(())
+}
+
+/// Serves every connection of the listener a session of its own, seeded by the seed and the number of the connection.
+/// Sessions idle for longer than the timeout are closed.
+pub fn serve(listener: TcpListener, variant: Variant, paytable: Paytable, seed: u64, credits: u64, timeout: Duration) -> io::Result<()> {
+    for (session, stream) in listener.incoming().enumerate() {
+        match stream {
+            Ok(stream) => {
+                let seed = seed.wrapping_add(session as u64);
+
+                // the peer may already be gone, which only ends its own session.
+                match stream.peer_addr() {
+                    Ok(peer) => log::info!("New session from `{peer}` with seed {seed}."),
+                    Err(error) => {
+                        log::error!("Error (from connection): {}.", error);
+
+                        continue;
+                    },
+                }
+
+                if let Err(error) = stream.set_read_timeout(Some(timeout)) {
+                    log::error!("Error (from connection): {}.", error);
+
+                    continue;
+                }
+
+                let machine = Machine::new(variant, paytable.clone(), credits, seed);
+
+                thread::spawn(move || {
+                    if let Err(error) = handle_connection(machine, stream) {
+                        log::error!("Error: {}", error);
+                    }
+                });
+            },
+            Err(error) => {
+                log::error!("Error (from connection): {}.", error);
+            }
+        }
+    }
+
+    Ok(())
+}
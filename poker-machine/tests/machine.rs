@@ -0,0 +1,153 @@
+use std::{io::{BufRead, BufReader, Read, Write}, net::{TcpListener, TcpStream}, thread, time::{Duration, Instant}};
+
+use poker_base::{Category, GameVariant, Paytable, Variant};
+use poker_machine::{machine::{Machine, MachineError, MAX_BET, SHORT_BET_ROYAL_PAYOUT}, protocol::{self, Request, Response}, server};
+
+fn machine(seed: u64) -> Machine {
+    Machine::new(Variant::JacksOrBetter, Paytable::jacks_or_better_9_6(), 100, seed)
+}
+
+/// A seeded machine replays the same session, and every hand is paid by the paytable times the bet.
+#[test]
+fn machine_replays_sessions_and_pays_by_paytable() {
+    let (mut first, mut second) = (machine(42), machine(42));
+    let paytable = Paytable::jacks_or_better_9_6();
+
+    for _ in 0..50 {
+        let hand = first.deal(5).unwrap();
+        assert_eq!(second.deal(5).unwrap(), hand);
+
+        let draw = first.draw(&[0, 1]).unwrap();
+        assert_eq!(second.draw(&[0, 1]).unwrap(), draw);
+
+        assert_eq!(draw.hand[..2], hand[..2]);
+        assert!(draw.hand[2..].iter().all(|card| !hand.contains(card)));
+        assert_eq!(draw.win, 5 * paytable.score(Variant::JacksOrBetter.evaluate(draw.hand.to_vec())) as u64);
+
+        if first.credits() < 5 {
+            break;
+        }
+    }
+
+    assert_ne!(machine(1).deal(1).unwrap(), machine(2).deal(1).unwrap());
+}
+
+/// The royal flush pays its full amount only at the max bet; every other category pays the same per coin at any bet.
+#[test]
+fn royal_flush_bonus_requires_max_bet() {
+    let machine = machine(0);
+    let paytable = machine.paytable();
+    let royal = paytable.payouts.iter().position(|payout| payout.category == Category::RoyalFlush).unwrap();
+
+    assert_eq!(machine.payout(royal, MAX_BET), 800);
+
+    for bet in 1..MAX_BET {
+        assert_eq!(machine.payout(royal, bet), SHORT_BET_ROYAL_PAYOUT);
+
+        for category in (0..paytable.payouts.len()).filter(|&category| category != royal) {
+            assert_eq!(machine.payout(category, bet), paytable.payouts[category].payout);
+        }
+    }
+
+    let win2day = Machine::new(Variant::JacksOrBetter, Paytable::win2day(), 100, 0);
+    assert_eq!(win2day.payout(royal, 1), 250);
+}
+
+/// Bets must be covered by the credits, and hands must be dealt and drawn alternately.
+#[test]
+fn machine_enforces_rules() {
+    let mut machine = Machine::new(Variant::JacksOrBetter, Paytable::jacks_or_better_9_6(), 3, 7);
+
+    assert_eq!(machine.draw(&[]), Err(MachineError::NoHand));
+    assert_eq!(machine.deal(0), Err(MachineError::InvalidBet(0)));
+    assert_eq!(machine.deal(5), Err(MachineError::InsufficientCredits { bet: 5, credits: 3 }));
+
+    machine.deal(3).unwrap();
+
+    assert_eq!(machine.credits(), 0);
+    assert_eq!(machine.deal(1), Err(MachineError::HandInPlay));
+    assert_eq!(machine.draw(&[5]), Err(MachineError::InvalidHold(5)));
+
+    let draw = machine.draw(&[0, 1, 2, 3, 4]).unwrap();
+
+    assert_eq!(machine.credits(), draw.win);
+    assert!(machine.hand().is_none());
+}
+
+/// Requests are answered line by line, malformed ones with an error.
+#[test]
+fn protocol_answers_each_line() {
+    let mut machine = machine(3);
+    let input = "{\"action\":\"deal\",\"bet\":1}\n\n{\"action\":\"draw\",\"hold\":[]}\n{\"action\":\"shuffle\"}\n{\"action\":\"balance\"}\n";
+    let mut output = Vec::new();
+
+    protocol::serve(&mut machine, input.as_bytes(), &mut output).unwrap();
+
+    let responses: Vec<Response> = String::from_utf8(output).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+
+    assert_eq!(responses.len(), 4);
+    assert!(matches!(responses[0], Response::Dealt { credits: 99, .. }));
+    assert!(matches!(responses[1], Response::Drawn { .. }));
+    assert!(matches!(responses[2], Response::Error { .. }));
+    assert_eq!(responses[3], Response::Balance { credits: machine.credits() });
+
+    assert_eq!(
+        protocol::respond(&mut machine, Request::Draw { hold: vec![] }),
+        Response::Error { message: MachineError::NoHand.to_string() }
+    );
+}
+
+/// A session is played over a real TCP connection to a server on a free port.
+#[test]
+fn server_plays_sessions_over_tcp() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    thread::spawn(move || server::serve(listener, Variant::JacksOrBetter, Paytable::jacks_or_better_9_6(), 42, 10, server::SESSION_TIMEOUT));
+
+    let connection = TcpStream::connect(address).unwrap();
+    let mut lines = BufReader::new(connection.try_clone().unwrap()).lines();
+    let mut request = |request: &str| -> Response {
+        writeln!(&connection, "{request}").unwrap();
+
+        serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap()
+    };
+
+    let Response::Dealt { hand, credits: 9 } = request("{\"action\":\"deal\",\"bet\":1}") else {
+        panic!("expected a dealt hand");
+    };
+
+    let Response::Drawn { hand: drawn, win, credits, .. } = request("{\"action\":\"draw\",\"hold\":[0,1,2,3,4]}") else {
+        panic!("expected a drawn hand");
+    };
+
+    assert_eq!(drawn, hand);
+    assert_eq!(credits, 9 + win);
+    assert_eq!(request("{\"action\":\"balance\"}"), Response::Balance { credits });
+
+    // the first session of the server is seeded by its seed.
+    assert_eq!(machine(42).deal(1).unwrap(), hand);
+}
+
+/// An idle session is closed after the timeout, while the server keeps serving new sessions.
+#[test]
+fn idle_sessions_time_out() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let timeout = Duration::from_millis(200);
+
+    thread::spawn(move || server::serve(listener, Variant::JacksOrBetter, Paytable::jacks_or_better_9_6(), 42, 10, timeout));
+
+    let start = Instant::now();
+    let mut idle = TcpStream::connect(address).unwrap();
+
+    // the server closes the connection without a response.
+    assert_eq!(idle.read(&mut [0u8; 1]).unwrap(), 0);
+    assert!(start.elapsed() >= timeout);
+
+    let connection = TcpStream::connect(address).unwrap();
+    writeln!(&connection, "{{\"action\":\"balance\"}}").unwrap();
+
+    let line = BufReader::new(connection).lines().next().unwrap().unwrap();
+    assert_eq!(serde_json::from_str::<Response>(&line).unwrap(), Response::Balance { credits: 10 });
+}
@@ -0,0 +1,297 @@
+//! The jobs computed by the farm: their states, the scheduling of blocks and the submission of computed blocks.
+
+use core::fmt;
+use std::{collections::HashSet, error::Error, fs, io, path::{Path, PathBuf}};
+
+use itertools::Itertools;
+use serde::{Serialize, Deserialize};
+use rand::prelude::SliceRandom;
+
+use poker_base::*;
+use poker_strategy::video_poker;
+
+pub const STD_BLOCK_SIZE: usize = 250usize;
+/// The directory the states of the jobs of a jobs file are kept in, one `<job>.json` each.
+pub const STATE_DIRECTORY: &str = "states";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComputationState {
+    /// The game being computed.
+    #[serde(default)]
+    pub variant: Variant,
+
+    /// The paytable being computed with, the default paytable of the variant if `None`.
+    #[serde(default)]
+    pub paytable: Option<Paytable>,
+
+    /// The computed moves.
+    pub computed: HashSet<ComputedMove>,
+
+    /// The remaining computation blocks.
+    pub remaining: HashSet<[Card; 5]>
+}
+
+impl ComputationState {
+    pub fn new(variant: Variant, paytable: Option<Paytable>) -> Result<Self, String> {
+        Ok(Self {
+            variant,
+            paytable,
+            computed: HashSet::new(),
+            remaining: patterns(&variant)?,
+        })
+    }
+}
+
+/// All hands of [GameVariant::hand_size] cards dealt from the deck of the game. Blocks carry five card
+/// patterns, so games dealing another amount of cards are rejected.
+pub fn patterns(game: &impl GameVariant) -> Result<HashSet<[Card; 5]>, String> {
+    game.deck()
+        .into_iter()
+        .combinations(game.hand_size())
+        .map(|combination| combination.try_into().map_err(|_| format!("`{}` deals {} cards, but blocks carry five card patterns.", game.name(), game.hand_size())))
+        .collect()
+}
+
+impl fmt::Display for ComputationState {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}: {} patterns computed; {} remaining.", self.variant, self.computed.len(), self.remaining.len())
+    }
+}
+
+/// The configuration of a job, as listed in a jobs file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobConfig {
+    /// Names the state file of the job, so it must be a plain file name stem (see [state_file]).
+    pub name: String,
+    /// The name of the variant as on the command line (e.g. `jacks-or-better`), the default variant if `None`.
+    #[serde(default)]
+    pub variant: Option<String>,
+    /// The name of a paytable preset or the path to a JSON paytable, the default paytable of the variant if `None`.
+    #[serde(default)]
+    pub paytable: Option<String>,
+    /// The relative share of blocks the job is served; a job of priority 0 is paused.
+    #[serde(default = "JobConfig::default_priority")]
+    pub priority: usize,
+}
+
+impl JobConfig {
+    pub fn default_priority() -> usize {
+        1
+    }
+}
+
+/// The state file of the job of the given name within the directory. Names that are not a plain file
+/// name stem (e.g. `../x` or an absolute path) are rejected, keeping the states within the directory.
+pub fn state_file(directory: impl AsRef<Path>, name: &str) -> Result<PathBuf, String> {
+    let plain = !name.is_empty()
+        && !name.starts_with('.')
+        && name.chars().all(|character| character.is_ascii_alphanumeric() || matches!(character, '-' | '_' | '.'));
+
+    match plain {
+        true => Ok(directory.as_ref().join(format!("{name}.json"))),
+        false => Err(format!("Invalid job name `{name}` (expected letters, digits, `-`, `_` and `.`).")),
+    }
+}
+
+/// A named game being computed by the farm, with its own state.
+#[derive(Debug)]
+pub struct Job {
+    pub name: String,
+    pub priority: usize,
+    pub state_file: PathBuf,
+    pub state: ComputationState,
+    /// The amount of blocks sent out.
+    pub served: usize,
+    /// The amount of remaining patterns when the state was last saved.
+    pub last_saved: usize,
+}
+
+impl Job {
+    pub fn new(name: String, priority: usize, state_file: PathBuf, state: ComputationState) -> Self {
+        Self {
+            name,
+            priority,
+            state_file,
+            last_saved: state.remaining.len(),
+            state,
+            served: 0,
+        }
+    }
+
+    /// Loads the state of the job from its state file, creating it if there is none. The game is resolved
+    /// like on the command line (see [game::resolve]), e.g. a paytable alone names the variant it is made for.
+    pub fn load(config: JobConfig, state_file: PathBuf) -> Result<Self, Box<dyn Error>> {
+        let game = match (config.variant.as_deref(), config.paytable.as_deref()) {
+            (Some(variant), Some(paytable)) => format!("{variant}:{paytable}"),
+            (Some(variant), None) => variant.parse::<Variant>()?.to_string(),
+            (None, Some(paytable)) => paytable.to_string(),
+            (None, None) => Variant::default().to_string(),
+        };
+
+        let (variant, paytable) = game::resolve(&game)?;
+        // the default paytable of the variant is kept implicit, as in states predating paytables.
+        let paytable = (paytable != variant.paytable()).then_some(paytable);
+
+        let state = load_state(&state_file, variant, paytable)?;
+
+        Ok(Self::new(config.name, config.priority, state_file, state))
+    }
+
+    pub fn paytable(&self) -> Paytable {
+        self.state.paytable.clone().unwrap_or_else(|| self.state.variant.paytable())
+    }
+
+    /// Recomputes the average score of the hold of a random move of the block, catching faulty clients.
+    pub fn verify(&self, computed: &ComputedBlock) -> bool {
+        let Some(r#move) = computed.moves.choose(&mut rand::thread_rng()) else {
+            return true;
+        };
+
+        let remaining = self.state.variant.deck().into_iter().filter(|card| !r#move.pattern.contains(card)).collect::<Vec<_>>();
+        let distribution = video_poker::calculate_distribution(&self.state.variant, &self.paytable(), &r#move.pattern, &r#move.keep, &remaining);
+
+        (video_poker::calculate_avg_score(&distribution) - r#move.average_score).abs() < 1e-9
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.priority != 0 && !self.state.remaining.is_empty()
+    }
+
+    pub fn save(&mut self) -> Result<(), Box<dyn Error>> {
+        save_state(&self.state_file, &self.state)?;
+
+        self.last_saved = self.state.remaining.len();
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Job {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "`{}` (priority {}, paytable `{}`) {}", self.name, self.priority, self.paytable().name, self.state)
+    }
+}
+
+/// Loads the jobs of a jobs file (a JSON list of [JobConfig]s), keeping their states in the given directory.
+pub fn load_jobs(file: impl AsRef<Path>, directory: impl AsRef<Path>) -> Result<Vec<Job>, Box<dyn Error>> {
+    let configs: Vec<JobConfig> = serde_json::from_str(&fs::read_to_string(file)?)?;
+
+    let mut jobs: Vec<Job> = Vec::with_capacity(configs.len());
+
+    for config in configs {
+        if jobs.iter().any(|job| job.name == config.name) {
+            return Err(format!("Duplicate job `{}`.", config.name).into());
+        }
+
+        let state_file = state_file(&directory, &config.name)?;
+
+        jobs.push(Job::load(config, state_file)?);
+    }
+
+    Ok(jobs)
+}
+
+/// The active job that has been served the fewest blocks relative to its priority.
+pub fn next_job(jobs: &mut [Job]) -> Option<&mut Job> {
+    jobs.iter_mut()
+        .filter(|job| job.is_active())
+        .min_by(|a, b| (a.served * b.priority).cmp(&(b.served * a.priority)))
+}
+
+/// Takes up to [STD_BLOCK_SIZE] random remaining patterns of the [next_job] as the next block to compute,
+/// or `None` if no job has work left.
+pub fn next_block(jobs: &mut [Job]) -> Option<ComputationBlock> {
+    let job = next_job(jobs)?;
+
+    let mut remaining: Vec<_> = job.state.remaining.iter().copied().collect();
+    remaining.shuffle(&mut rand::thread_rng());
+    remaining.truncate(STD_BLOCK_SIZE);
+
+    // TODO: potentially save a timestamp of last sent and if exceeded only then send again.
+
+    job.served += 1;
+
+    Some(ComputationBlock {
+        job: job.name.clone(),
+        variant: job.state.variant,
+        paytable: job.state.paytable.clone(),
+        patterns: remaining,
+    })
+}
+
+/// The job a computed block belongs to. Blocks of clients predating jobs carry no job name, which is unambiguous with a single job.
+pub fn find_job<'a>(jobs: &'a mut [Job], name: &str) -> Result<&'a mut Job, String> {
+    let single = jobs.len() == 1 && name.is_empty();
+
+    jobs
+        .iter_mut()
+        .find(|job| single || job.name == name)
+        .ok_or_else(|| format!("Received a block of unknown job `{name}`."))
+}
+
+/// Verifies the computed block and records its moves in the state of its job, returning the job and the
+/// amount of moves that had already been recorded.
+pub fn submit(jobs: &mut [Job], computed: ComputedBlock) -> Result<(&Job, usize), Box<dyn Error>> {
+    let job = find_job(jobs, &computed.job)?;
+
+    if computed.moves.len() != STD_BLOCK_SIZE {
+        log::warn!("Received a computed block of size {} (expected {}).", computed.moves.len(), STD_BLOCK_SIZE);
+    }
+
+    if !job.verify(&computed) {
+        return Err(format!("Rejected a block of job `{}` whose scores do not match the paytable.", job.name).into());
+    }
+
+    let mut duplicates = 0;
+
+    for optimal in computed.moves.into_iter() {
+        if job.state.remaining.remove(&optimal.pattern) {
+            job.state.computed.insert(optimal);
+        } else {
+            duplicates += 1;
+        }
+    }
+
+    Ok((job, duplicates))
+}
+
+/// Loads the state of the given game from the file, creating it if it does not exist. An existing state
+/// of another variant or paytable is rejected.
+pub fn load_state(file: impl AsRef<Path>, variant: Variant, paytable: Option<Paytable>) -> Result<ComputationState, Box<dyn Error>> {
+    let file = file.as_ref();
+
+    if file.exists() {
+        let state: ComputationState = serde_json::from_str(&fs::read_to_string(file)?)?;
+
+        if state.variant != variant {
+            return Err(format!("The existing state `{}` computes `{}`, not `{variant}`.", file.display(), state.variant).into());
+        }
+
+        if state.paytable.clone().unwrap_or_else(|| variant.paytable()) != paytable.clone().unwrap_or_else(|| variant.paytable()) {
+            return Err(format!("The existing state `{}` computes with a different paytable.", file.display()).into());
+        }
+
+        Ok(state)
+    } else {
+        log::warn!("No state `{}` found, creating new one for `{variant}`.", file.display());
+
+        let state = ComputationState::new(variant, paytable)?;
+
+        save_state(file, &state)?;
+        
+        Ok(state)
+    }
+}
+
+pub fn save_state(file: impl AsRef<Path>, state: &ComputationState) -> Result<(), Box<dyn Error>> {
+    let file = file.as_ref();
+
+    log::info!("Saving state to `{}`...", file.display());
+
+    fs::create_dir_all(file.parent().ok_or(io::Error::new(io::ErrorKind::NotFound, "No parent directory"))?)?;
+    fs::write(file, serde_json::to_string(&state)?)?;
+
+    log::info!("State has been saved.");
+
+    Ok(())
+}
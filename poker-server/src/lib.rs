@@ -0,0 +1,3 @@
+//! The server distributing the computation of video poker games among clients, block by block.
+
+pub mod job;
@@ -1,89 +1,13 @@
-
-use core::fmt;
-use std::{collections::HashSet, error::Error, fs, io::{self, Read}, net::{TcpListener, TcpStream}, path::Path, time::Duration};
-
-use itertools::Itertools;
-use serde::{Serialize, Deserialize};
-use rand::prelude::SliceRandom;
+use std::{env, error::Error, io::{self, Read}, net::{TcpListener, TcpStream}, path::{Path, PathBuf}, time::Duration};
 
 use poker_base::*;
+use poker_server::job::{self, Job, JobConfig, STATE_DIRECTORY, STD_BLOCK_SIZE};
 
-pub const STD_BLOCK_SIZE: usize = 250usize;
 pub const AUTOSAVE_THRESHOLD: usize = 16usize;
 pub const STATE_FILE: &str = "state.json";
 pub const SERVER_ADDRESS: &str = "0.0.0.0:5566";
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-struct ComputationState {
-    /// The computed moves.
-    computed: HashSet<ComputedMove>,
-
-    /// The remaining computation blocks.
-    remaining: HashSet<[Card; 5]>
-}
-
-impl Default for ComputationState {
-    fn default() -> Self {
-        let deck = Card::full_deck();
-        
-        let remaining: HashSet<_> = deck
-            .iter()
-            .combinations(5)
-            .map(|combination| {
-                let mut pattern = [Card { suit: Suit::Diamond, value: Value::Ace }; 5];
-
-                for index in 0..pattern.len() {
-                    pattern[index] = *combination[index];
-                }
-
-                pattern
-            }).collect();
-
-        Self {
-            computed: HashSet::new(),
-            remaining
-        }
-    }
-}
-
-impl fmt::Display for ComputationState {
-    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(formatter, "{} patterns computed; {} remaining.", self.computed.len(), self.remaining.len())
-    }
-}
-
-fn load_state(file: impl AsRef<Path>) -> Result<ComputationState, Box<dyn Error>> {
-    let file = file.as_ref();
-
-    if file.exists() {
-        let state = fs::read_to_string(file)?;
-
-        Ok(serde_json::from_str(&state)?)
-    } else {
-        log::warn!("No state found, creating new one.");
-
-        let state = ComputationState::default();
-
-        save_state(file, &state)?;
-        
-        Ok(state)
-    }
-}
-
-fn save_state(file: impl AsRef<Path>, state: &ComputationState) -> Result<(), Box<dyn Error>> {
-    let file = file.as_ref();
-
-    log::info!("Saving state to `{}`...", file.display());
-
-    fs::create_dir_all(file.parent().ok_or(io::Error::new(io::ErrorKind::NotFound, "No parent directory"))?)?;
-    fs::write(file, serde_json::to_string(&state)?)?;
-
-    log::info!("State has been saved.");
-
-    Ok(())
-}
-
-fn handle_connection(state: &mut ComputationState, mut connection: TcpStream) -> Result<TcpStream, Box<dyn Error>> {
+fn handle_connection(jobs: &mut [Job], mut connection: TcpStream) -> Result<TcpStream, Box<dyn Error>> {
     connection.set_read_timeout(Some(Duration::from_secs(10)))?;
 
     let mut operation = [0u8; 1];
@@ -93,27 +17,16 @@ fn handle_connection(state: &mut ComputationState, mut connection: TcpStream) ->
     if operation == [0u8] {
         log::info!("Received a computation request from `{}`.", connection.peer_addr()?);
 
-        let mut patterns = Vec::with_capacity(STD_BLOCK_SIZE);
-
-        let mut remaining: Vec<_> = state.remaining.iter().collect();
-        remaining.shuffle(&mut rand::thread_rng());
-        let mut remaining = remaining.into_iter();
-
-        for _ in 0..STD_BLOCK_SIZE {
-            match remaining.next() {
-                Some(pattern) => patterns.push(*pattern),
-                None => {
-                    log::warn!("No remaining blocks to compute!");
+        // without work, the connection is closed without a block, telling the client to retry later.
+        let Some(block) = job::next_block(jobs) else {
+            log::warn!("No remaining blocks to compute!");
 
-                    break;
-                }
-            }
-        }
-
-        // TODO: potentially save a timestamp of last sent and if exceeded only then send again.
+            return Ok(connection);
+        };
 
-        serde_json::to_writer(&mut connection, &ComputationBlock { patterns })?;
+        log::info!("Sending a block of job `{}` (`{}`).", block.job, block.variant);
 
+        serde_json::to_writer(&mut connection, &block)?;
 
         Ok(connection)
     } else if operation == [1u8] {
@@ -121,19 +34,13 @@ fn handle_connection(state: &mut ComputationState, mut connection: TcpStream) ->
         
         let computed: ComputedBlock = serde_json::from_reader(&mut connection)?;
 
-        if computed.moves.len() != STD_BLOCK_SIZE {
-            log::warn!("Received a computed block of size {} (expected {}).", computed.moves.len(), STD_BLOCK_SIZE);
-        }
+        let (job, duplicates) = job::submit(jobs, computed)?;
 
-        for optimal in computed.moves.into_iter() {
-            if state.remaining.remove(&optimal.pattern) {
-                state.computed.insert(optimal);
-            } else {
-                log::warn!("Received an alredy processed move from `{}`.", connection.peer_addr()?);
-            }
+        if duplicates != 0 {
+            log::warn!("Received {duplicates} alredy processed moves from `{}`.", connection.peer_addr()?);
         }
 
-        log::info!("After submission, state is: `{}`.", state);
+        log::info!("After submission, job is: {}", job);
 
         Ok(connection)
     } else {
@@ -142,15 +49,14 @@ fn handle_connection(state: &mut ComputationState, mut connection: TcpStream) ->
 
 }
 
-fn start() -> Result<(), Box<dyn Error>> {
+fn start(mut jobs: Vec<Job>) -> Result<(), Box<dyn Error>> {
     log::info!("A compute block size of {} will be used.", STD_BLOCK_SIZE);
-    log::info!("Loading state...");
-    let mut state = load_state(STATE_FILE)?;
-    log::info!("State loaded: {state}");
 
-    log::info!("Starting server on `{}`...", SERVER_ADDRESS);
+    for job in &jobs {
+        log::info!("Job loaded: {job}");
+    }
 
-    let mut last_saved = state.remaining.len();
+    log::info!("Starting server on `{}`...", SERVER_ADDRESS);
 
     let listener = TcpListener::bind(SERVER_ADDRESS)?;
 
@@ -159,13 +65,12 @@ fn start() -> Result<(), Box<dyn Error>> {
             Ok(stream) => {
                 log::info!("New connection from `{}`.", stream.peer_addr()?);
 
-                match handle_connection(&mut state, stream) {
+                match handle_connection(&mut jobs, stream) {
                     Ok(connection) => {
-
-                        if last_saved - state.remaining.len() > STD_BLOCK_SIZE * AUTOSAVE_THRESHOLD {
-                            save_state(STATE_FILE, &state)?;
-
-                            last_saved = state.remaining.len();
+                        for job in &mut jobs {
+                            if job.last_saved - job.state.remaining.len() > STD_BLOCK_SIZE * AUTOSAVE_THRESHOLD {
+                                job.save()?;
+                            }
                         }
 
                         log::info!("Connection from `{}` successfully handled.", connection.peer_addr()?);
@@ -184,6 +89,26 @@ fn start() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Loads the jobs given on the command line: either a single variant (computed into [STATE_FILE]) or a jobs file.
+fn jobs(argument: Option<String>) -> Result<Vec<Job>, Box<dyn Error>> {
+    let variant = match argument {
+        Some(argument) => match argument.parse::<Variant>() {
+            Ok(variant) => variant,
+            Err(_) if Path::new(&argument).exists() => {
+                log::info!("Loading jobs from `{argument}`...");
+
+                return job::load_jobs(argument, STATE_DIRECTORY);
+            },
+            Err(error) => return Err(error.into()),
+        },
+        None => Variant::default(),
+    };
+
+    let config = JobConfig { name: variant.name().to_owned(), variant: Some(variant.name().to_owned()), paytable: None, priority: JobConfig::default_priority() };
+
+    Ok(vec![Job::load(config, PathBuf::from(STATE_FILE))?])
+}
+
 fn main() {
     if let Err(error) = simple_logger::SimpleLogger::new().env().init() {
         eprintln!("Logger initialization failed: {}", error);
@@ -191,7 +116,17 @@ fn main() {
         std::process::exit(1);
     }
 
-    if let Err(error) = start() {
+    let jobs = match jobs(env::args().nth(1)) {
+        Ok(jobs) => jobs,
+        Err(error) => {
+            log::error!("Fatal: {}", error);
+            log::error!("Usage: poker-server [{} | <jobs file>]", Variant::ALL.map(|variant| variant.name()).join(" | "));
+
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(error) = start(jobs) {
         log::error!("Fatal: {}", error);
         
         std::process::exit(1);
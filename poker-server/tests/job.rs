@@ -0,0 +1,182 @@
+use std::{collections::HashSet, fs, path::PathBuf, sync::Arc};
+
+use poker_base::{parse_cards, parse_hand, Card, ComputedBlock, Evaluation, GameVariant, Paytable, Variant};
+use poker_server::job::{self, ComputationState, Job};
+use poker_strategy::video_poker;
+
+/// A state with a few remaining patterns, instead of all deals.
+fn state(variant: Variant, paytable: Option<Paytable>) -> ComputationState {
+    ComputationState {
+        variant,
+        paytable,
+        computed: HashSet::new(),
+        remaining: HashSet::from([parse_hand("Ah Kh Qh Jh 2c").unwrap(), parse_hand("2h 7c 9d Js Kd").unwrap()]),
+    }
+}
+
+fn job(name: &str, priority: usize) -> Job {
+    Job::new(name.to_string(), priority, PathBuf::from(format!("{name}.json")), state(Variant::JacksOrBetter, None))
+}
+
+/// A fresh directory for the state files of a test.
+fn directory(test: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("poker-server-{}-{test}", std::process::id()));
+
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+
+    directory
+}
+
+#[test]
+fn job_names_must_be_plain_file_stems() {
+    assert_eq!(job::state_file("states", "deuces-wild_2.v1").unwrap(), PathBuf::from("states/deuces-wild_2.v1.json"));
+
+    for name in ["", "../x", "..", ".hidden", "a/b", "a\\b", "/tmp/x", "C:x"] {
+        assert!(job::state_file("states", name).is_err(), "accepted `{name}`");
+    }
+}
+
+/// Jobs are served in proportion to their priority; paused and finished jobs are not served.
+#[test]
+fn blocks_are_served_by_priority() {
+    let mut finished = job("finished", 5);
+    finished.state.remaining.clear();
+
+    let mut jobs = vec![job("low", 1), job("high", 2), job("paused", 0), finished];
+
+    for _ in 0..30 {
+        let block = job::next_block(&mut jobs).unwrap();
+
+        assert_eq!(block.patterns.len(), 2);
+        assert_eq!(block.variant, Variant::JacksOrBetter);
+    }
+
+    assert_eq!(jobs.iter().map(|job| job.served).collect::<Vec<_>>(), vec![10, 20, 0, 0]);
+
+    for job in &mut jobs {
+        job.state.remaining.clear();
+    }
+
+    assert_eq!(job::next_block(&mut jobs), None);
+}
+
+/// Computed blocks are recorded in the state of the job they name.
+#[test]
+fn submissions_are_routed_to_their_job() {
+    let mut jobs = vec![job("a", 1), job("b", 1)];
+
+    let patterns = vec![parse_hand("Ah Kh Qh Jh 2c").unwrap()];
+    let mut computed = video_poker::compute_combinations(Variant::JacksOrBetter, &Arc::new(Variant::JacksOrBetter.paytable()), patterns);
+    computed.job = "b".to_string();
+
+    let (job, duplicates) = job::submit(&mut jobs, computed.clone()).unwrap();
+    assert_eq!((job.name.as_str(), duplicates), ("b", 0));
+    assert_eq!((jobs[1].state.computed.len(), jobs[1].state.remaining.len()), (1, 1));
+    assert_eq!(jobs[0].state.computed.len(), 0);
+
+    assert_eq!(job::submit(&mut jobs, computed.clone()).unwrap().1, 1);
+
+    let unknown = ComputedBlock { job: "c".to_string(), moves: Vec::new() };
+    assert_eq!(job::submit(&mut jobs, unknown).unwrap_err().to_string(), "Received a block of unknown job `c`.");
+
+    // blocks without a job name are only accepted with a single job.
+    let unnamed = ComputedBlock { job: String::new(), moves: Vec::new() };
+    assert!(job::submit(&mut jobs, unnamed.clone()).is_err());
+    assert_eq!(job::submit(&mut jobs[..1], unnamed).unwrap().0.name, "a");
+
+    // a block whose scores do not match the paytable is rejected.
+    computed.moves[0].average_score += 1f64;
+    assert!(job::submit(&mut jobs, computed).is_err());
+}
+
+/// Jobs files name variants and paytables like the command line; existing states must match their job.
+#[test]
+fn jobs_files_are_loaded() {
+    let directory = directory("load");
+    let paytable_file = directory.join("paytable.json");
+    let jobs_file = directory.join("jobs.json");
+
+    fs::write(&paytable_file, serde_json::to_string(&Paytable::bonus_poker_8_5()).unwrap()).unwrap();
+
+    let states = directory.join("states");
+    job::save_state(states.join("deuces.json"), &state(Variant::DeucesWild, None)).unwrap();
+    job::save_state(states.join("jokers.json"), &state(Variant::JokerPoker, None)).unwrap();
+    job::save_state(states.join("bonus.json"), &state(Variant::JacksOrBetter, Some(Paytable::bonus_poker_8_5()))).unwrap();
+
+    fs::write(&jobs_file, format!(r#"[
+        {{ "name": "deuces", "variant": "deuces-wild", "priority": 2 }},
+        {{ "name": "jokers", "variant": "JokerPoker" }},
+        {{ "name": "bonus", "paytable": {:?} }},
+        {{ "name": "deuces-full-pay", "paytable": "deuces-wild-full-pay" }}
+    ]"#, paytable_file.display().to_string())).unwrap();
+
+    let jobs = job::load_jobs(&jobs_file, &states).unwrap();
+
+    assert_eq!(
+        jobs.iter().map(|job| job.state.variant).collect::<Vec<_>>(),
+        vec![Variant::DeucesWild, Variant::JokerPoker, Variant::JacksOrBetter, Variant::DeucesWild]
+    );
+    assert_eq!(jobs.iter().map(|job| job.priority).collect::<Vec<_>>(), vec![2, 1, 1, 1]);
+    assert_eq!(jobs[2].paytable(), Paytable::bonus_poker_8_5());
+    assert_eq!(jobs[3].paytable(), Paytable::deuces_wild_full_pay());
+
+    // a paytable made for another variant is rejected.
+    fs::write(&jobs_file, r#"[{ "name": "mixed", "variant": "jacks-or-better", "paytable": "deuces-wild-full-pay" }]"#).unwrap();
+    assert!(job::load_jobs(&jobs_file, &states).is_err());
+    assert!(!states.join("mixed.json").exists());
+
+    // a state of another variant or paytable is not resumed.
+    fs::write(&jobs_file, r#"[{ "name": "deuces", "variant": "joker-poker" }]"#).unwrap();
+    assert!(job::load_jobs(&jobs_file, &states).is_err());
+
+    fs::write(&jobs_file, r#"[{ "name": "bonus", "paytable": "jacks-or-better-9-6" }]"#).unwrap();
+    assert!(job::load_jobs(&jobs_file, &states).is_err());
+
+    // names escaping the state directory are rejected before anything is written.
+    fs::write(&jobs_file, r#"[{ "name": "../escaped" }]"#).unwrap();
+    assert!(job::load_jobs(&jobs_file, &states).is_err());
+    assert!(!directory.join("escaped.json").exists());
+
+    fs::write(&jobs_file, r#"[{ "name": "a" }, { "name": "a" }]"#).unwrap();
+    job::save_state(states.join("a.json"), &state(Variant::JacksOrBetter, None)).unwrap();
+    assert!(job::load_jobs(&jobs_file, &states).is_err());
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+/// A game of five cards dealt from a deck of six, or of six cards.
+#[derive(Clone, Copy)]
+struct Small(usize);
+
+impl GameVariant for Small {
+    fn name(&self) -> &'static str {
+        "small"
+    }
+
+    fn deck(&self) -> Vec<Card> {
+        parse_cards("Ah Kh Qh Jh Th 9h").unwrap()
+    }
+
+    fn hand_size(&self) -> usize {
+        self.0
+    }
+
+    fn evaluate(&self, cards: Vec<Card>) -> Evaluation {
+        Variant::JacksOrBetter.evaluate(cards)
+    }
+
+    fn paytable(&self) -> Paytable {
+        Variant::JacksOrBetter.paytable()
+    }
+}
+
+#[test]
+fn patterns_are_the_hands_of_the_game() {
+    let patterns = job::patterns(&Small(5)).unwrap();
+
+    assert_eq!(patterns.len(), 6);
+    assert!(patterns.contains(&parse_hand("Ah Kh Qh Jh Th").unwrap()));
+
+    assert_eq!(job::patterns(&Small(6)).unwrap_err(), "`small` deals 6 cards, but blocks carry five card patterns.");
+}
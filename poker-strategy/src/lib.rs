@@ -0,0 +1,4 @@
+//! Optimal draw decisions, shared by the farm and the tools built on it.
+
+pub mod solver;
+pub mod video_poker;
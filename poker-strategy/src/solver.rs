@@ -0,0 +1,86 @@
+//! A generic solver for single draw decisions: which cards to keep to maximise the expected payoff.
+//!
+//! The solver knows nothing about the game being played. It is given the dealt hand, the cards that may
+//! be drawn, a [DrawRule] restricting the discards and a payoff function of the final hand. Video poker
+//! (paying out by a paytable), single draw lowball (e.g. the chance of making a seven low) and custom
+//! games thus share one engine.
+
+use core::cmp::Ordering;
+
+use itertools::Itertools;
+use poker_base::Card;
+
+/// Restricts which discards are allowed in a draw.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct DrawRule {
+    /// The most cards that may be replaced.
+    pub max_discards: usize,
+}
+
+impl DrawRule {
+    /// Any amount of cards may be replaced, as in video poker.
+    pub const ANY: DrawRule = DrawRule { max_discards: usize::MAX };
+
+    /// Whether discarding the given amount of cards is allowed.
+    pub fn allows(&self, discards: usize) -> bool {
+        discards <= self.max_discards
+    }
+}
+
+/// A way to play a hand and its expected payoff.
+#[derive(PartialEq, Debug, Clone)]
+pub struct DiscardOption {
+    /// The indices (into the hand) of the kept cards; all others are discarded and replaced.
+    pub keep: Vec<usize>,
+    pub expected_value: f64,
+}
+
+/// All final hands reachable by keeping the cards at the given indices and drawing replacements from the deck.
+/// The kept cards come first, followed by the drawn cards.
+pub fn draws<'a>(hand: &'a [Card], deck: &'a [Card], keep: &'a [usize]) -> impl Iterator<Item = Vec<Card>> + 'a {
+    deck.iter()
+        .copied()
+        .combinations(hand.len() - keep.len())
+        .map(move |drawn| keep.iter().map(|&index| hand[index]).chain(drawn).collect())
+}
+
+/// The expected payoff of keeping the cards at the given indices, every draw from the deck being equally likely,
+/// or `None` if the deck has too few cards to draw the replacements.
+pub fn expected_value(hand: &[Card], deck: &[Card], keep: &[usize], payoff: impl Fn(&[Card]) -> f64) -> Option<f64> {
+    let (total, amount) = draws(hand, deck, keep).fold((0f64, 0u64), |(total, amount), hand| (total + payoff(&hand), amount + 1));
+
+    (amount > 0).then(|| total / amount as f64)
+}
+
+/// The indices of the cards kept by every way to play a hand of the given size allowed by the rule, ordered by
+/// the amount of kept cards and then lexicographically.
+pub fn holds(hand_size: usize, rule: DrawRule) -> impl Iterator<Item = Vec<usize>> {
+    (0..=hand_size)
+        .filter(move |&kept| rule.allows(hand_size - kept))
+        .flat_map(move |kept| (0..hand_size).combinations(kept))
+}
+
+/// Orders holds of equal value by preference: the hold keeping the fewest cards comes first, then the one
+/// of the lowest mask of kept indices.
+pub fn tie_break(a: &[usize], b: &[usize]) -> Ordering {
+    let mask = |keep: &[usize]| keep.iter().fold(0usize, |mask, index| mask | (1 << index));
+
+    a.len().cmp(&b.len()).then_with(|| mask(a).cmp(&mask(b)))
+}
+
+/// Computes the expected payoff of every way to play the hand allowed by the rule, in the order of [holds].
+/// Holds discarding more cards than the deck can replace are left out. The deck must not contain the cards of the hand.
+pub fn solve(hand: &[Card], deck: &[Card], rule: DrawRule, payoff: impl Fn(&[Card]) -> f64) -> Vec<DiscardOption> {
+    holds(hand.len(), rule)
+        .filter_map(|keep| Some(DiscardOption { expected_value: expected_value(hand, deck, &keep, &payoff)?, keep }))
+        .collect()
+}
+
+/// The best way to play the hand. Of equally good options, the first by [tie_break] is chosen. There is always
+/// an option, as every rule allows keeping all cards.
+pub fn optimal(hand: &[Card], deck: &[Card], rule: DrawRule, payoff: impl Fn(&[Card]) -> f64) -> DiscardOption {
+    solve(hand, deck, rule, payoff)
+        .into_iter()
+        .max_by(|a, b| a.expected_value.total_cmp(&b.expected_value).then_with(|| tie_break(&b.keep, &a.keep)))
+        .expect("keeping all cards is always allowed")
+}
@@ -0,0 +1,142 @@
+//! Scoring and optimal holds of video poker hands, as computed by the farm's clients.
+//!
+//! ```no_run
+//! use poker_base::{parse_hand, Deck, GameVariant, Variant};
+//! use poker_strategy::video_poker;
+//!
+//! let shown = parse_hand("Ah Kh Qh Jh 2c").unwrap();
+//! let remaining = Deck::standard().remaining(&shown);
+//!
+//! let optimal = video_poker::calculate_optimal(&Variant::JacksOrBetter, &Variant::JacksOrBetter.paytable(), &remaining, &shown);
+//! assert_eq!(optimal.keep, vec![0, 1, 2, 3]);
+//! ```
+
+use std::{collections::BTreeMap, sync::Arc, thread};
+
+use poker_base::{Card, ComputedBlock, ComputedMove, GameVariant, Paytable};
+
+use crate::solver;
+
+/// Calculates the score (payout per coin) of a final hand.
+pub fn calculate_score(game: &impl GameVariant, paytable: &Paytable, hand: &[Card]) -> usize {
+    paytable.score(game.evaluate(hand.to_vec()))
+}
+
+/// Calculates the distribution of the scores (score: amount of draws) when keeping the given cards of the shown
+/// ones and drawing the others from the remaining cards.
+pub fn calculate_distribution(game: &impl GameVariant, paytable: &Paytable, shown: &[Card], keep: &[usize], remaining: &[Card]) -> BTreeMap<usize, u64> {
+    let mut distribution = BTreeMap::new();
+
+    for hand in solver::draws(shown, remaining, keep) {
+        *distribution.entry(calculate_score(game, paytable, &hand)).or_default() += 1;
+    }
+
+    distribution
+}
+
+/// Calculates the average score of the given distribution.
+pub fn calculate_avg_score(distribution: &BTreeMap<usize, u64>) -> f64 {
+    let total: u64 = distribution.iter().map(|(&score, &amount)| score as u64 * amount).sum();
+    let amount: u64 = distribution.values().sum();
+
+    (total as f64) / (amount as f64)
+}
+
+/// Calculates the hold with the highest average score of the shown cards, drawing from the remaining cards
+/// (i.e. the deck without the shown cards). Of equally good holds, the first by [solver::tie_break] is chosen.
+/// The draws of each hold are enumerated once, into the distribution of its scores.
+pub fn calculate_optimal(game: &impl GameVariant, paytable: &Paytable, remaining: &[Card], shown: &[Card; 5]) -> ComputedMove {
+    solver::holds(shown.len(), solver::DrawRule::ANY)
+        .map(|keep| {
+            let distribution = calculate_distribution(game, paytable, shown, &keep, remaining);
+
+            ComputedMove { pattern: *shown, average_score: calculate_avg_score(&distribution), keep, distribution }
+        })
+        .max_by(|a, b| a.average_score.total_cmp(&b.average_score).then_with(|| solver::tie_break(&b.keep, &a.keep)))
+        .expect("every hand can be played")
+}
+
+/// The cards that may be drawn: the deck without the shown and the dead (e.g. seen or exposed) cards.
+pub fn remaining(deck: &[Card], shown: &[Card], dead: &[Card]) -> Vec<Card> {
+    deck.iter()
+        .filter(|card| !shown.contains(card) && !dead.contains(card))
+        .copied()
+        .collect()
+}
+
+/// The optimal hold of a hand with and without dead cards removed from the deck.
+#[derive(PartialEq, Debug, Clone)]
+pub struct DeadCardMove {
+    /// Drawing from the full deck, as in the standard table.
+    pub standard: ComputedMove,
+    /// Drawing from the deck without the dead cards.
+    pub adjusted: ComputedMove,
+}
+
+impl DeadCardMove {
+    /// The change of the expected value by removing the dead cards.
+    pub fn difference(&self) -> f64 {
+        self.adjusted.average_score - self.standard.average_score
+    }
+
+    /// Whether removing the dead cards changes the optimal hold.
+    pub fn hold_changed(&self) -> bool {
+        self.adjusted.keep != self.standard.keep
+    }
+}
+
+/// Calculates the optimal hold of the shown cards both drawing from the full deck and from the deck
+/// without the dead cards.
+pub fn calculate_dead_card_move(game: &impl GameVariant, paytable: &Paytable, shown: &[Card; 5], dead: &[Card]) -> DeadCardMove {
+    let deck = game.deck();
+
+    DeadCardMove {
+        standard: calculate_optimal(game, paytable, &remaining(&deck, shown, &[]), shown),
+        adjusted: calculate_optimal(game, paytable, &remaining(&deck, shown, dead), shown),
+    }
+}
+
+/// Calculates the optimal holds of all the given patterns, spread over all available cores.
+pub fn compute_combinations<G: GameVariant + Copy + 'static>(game: G, paytable: &Arc<Paytable>, combinations: Vec<[Card; 5]>) -> ComputedBlock {
+    compute_combinations_with_dead(game, paytable, combinations, &[])
+}
+
+/// Calculates the optimal holds of all the given patterns drawing from the deck without the dead cards,
+/// spread over all available cores. Patterns containing dead cards are skipped.
+pub fn compute_combinations_with_dead<G: GameVariant + Copy + 'static>(game: G, paytable: &Arc<Paytable>, combinations: Vec<[Card; 5]>, dead: &[Card]) -> ComputedBlock {
+    let mut combinations: Vec<[Card; 5]> = combinations.into_iter().filter(|shown| !shown.iter().any(|card| dead.contains(card))).collect();
+    let deck = Arc::new(remaining(&game.deck(), &[], dead));
+    let mut moves: Vec<_> = Vec::with_capacity(combinations.len()); // shown: chosen
+
+    let cpus = thread::available_parallelism().map(|parallelism| parallelism.get()).unwrap_or(1);
+    let combinations_per_cpu = combinations.len() / cpus;
+
+    let mut handles = Vec::with_capacity(cpus);
+
+    for _ in 0..cpus {
+        let thread_combinations: Vec<_> = combinations.drain(0..combinations_per_cpu).collect();
+        let deck = deck.clone();
+        let paytable = paytable.clone();
+
+        // TODO: Spwaning threads is expensive. potentially spawn at start and then alwys move load to them.
+        handles.push(thread::spawn(move || {
+            let mut moves: Vec<_> = Vec::with_capacity(thread_combinations.len());
+
+            for shown in thread_combinations {
+                moves.push(calculate_optimal(&game, &paytable, &remaining(&deck, &shown, &[]), &shown));
+            }
+
+            moves
+        }));
+    }
+
+    for shown in combinations {
+        moves.push(calculate_optimal(&game, paytable, &remaining(&deck, &shown, &[]), &shown));
+    }
+
+    for handle in handles {
+        moves.extend(handle.join().unwrap());
+    }
+
+    ComputedBlock { job: String::new(), moves }
+}
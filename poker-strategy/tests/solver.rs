@@ -0,0 +1,76 @@
+use poker_base::{compute_deuce_to_seven_rank, parse_hand, Card, Deck, GameVariant, HandCategory, Paytable, Variant};
+use poker_strategy::solver::{self, DrawRule};
+
+/// The solver finds the published expected values of full-pay Jacks or Better holds, counted out of the 47 draws.
+#[test]
+fn matches_jacks_or_better_values() {
+    let variant = Variant::JacksOrBetter;
+    let paytable = Paytable::jacks_or_better_9_6();
+
+    let payoff = |cards: &[Card]| paytable.score(variant.evaluate(cards.to_vec())) as f64;
+
+    let expected = [
+        // the royal flush, the other eight hearts make a flush, the other tens a straight and the other 12 high cards a pair.
+        ("Ah Kh Qh Jh 9c", vec![0, 1, 2, 3], (800 + 8 * 6 + 3 * 4 + 12) as f64 / 47f64),
+        // the four and nine of hearts make a straight flush, the other six a straight and seven hearts a flush.
+        ("5h 6h 7h 8h Tc", vec![0, 1, 2, 3], (2 * 50 + 6 * 4 + 7 * 6) as f64 / 47f64),
+        ("Ac Ad As Ah 2c", vec![0, 1, 2, 3], 25f64),
+        ("Ah Kh Qh Jh Th", vec![0, 1, 2, 3, 4], 800f64),
+    ];
+
+    for (dealt, keep, expected_value) in expected {
+        let dealt = parse_hand(dealt).unwrap();
+        let deck = Deck::standard().remaining(&dealt);
+
+        let options = solver::solve(&dealt, &deck, DrawRule::ANY, payoff);
+        assert_eq!(options.len(), 32);
+
+        let option = options.iter().find(|option| option.keep == keep).unwrap();
+        assert!((option.expected_value - expected_value).abs() < 1e-9, "{keep:?} of {dealt:?} was {}", option.expected_value);
+
+        let optimal = solver::optimal(&dealt, &deck, DrawRule::ANY, payoff);
+        assert!((optimal.expected_value - expected_value).abs() < 1e-9, "{:?} of {dealt:?}", optimal.keep);
+    }
+}
+
+/// Single draw 2-7 lowball, paying off when making a seven low.
+#[test]
+fn solves_lowball_draws() {
+    let payoff = |cards: &[Card]| {
+        let rank = compute_deuce_to_seven_rank(cards.to_vec());
+
+        (rank.category() == HandCategory::HighCard && rank.values()[0] <= poker_base::Value::Seven) as u8 as f64
+    };
+
+    let dealt = parse_hand("7h 5c 4d 3s Kh").unwrap();
+    let deck = Deck::standard().remaining(&dealt);
+
+    let optimal = solver::optimal(&dealt, &deck, DrawRule::ANY, payoff);
+
+    // drawing to 7-5-4-3 only makes a seven low with one of the four deuces, as a six makes a straight.
+    assert_eq!(optimal.keep, vec![0, 1, 2, 3]);
+    assert!((optimal.expected_value - 4f64 / 47f64).abs() < 1e-12);
+
+    let options = solver::solve(&dealt, &deck, DrawRule { max_discards: 1 }, payoff);
+    assert_eq!(options.len(), 6);
+}
+
+/// Holds the deck cannot replace are no options, rather than options of an undefined value.
+#[test]
+fn short_decks_leave_out_impossible_draws() {
+    let dealt = parse_hand("7h 5c 4d 3s Kh").unwrap();
+    let deck = parse_hand("2c 2d 2h 2s 9c").unwrap();
+    let payoff = |cards: &[Card]| cards.iter().filter(|card| card.value == poker_base::Value::Two).count() as f64;
+
+    assert_eq!(solver::expected_value(&dealt, &deck[..1], &[], payoff), None);
+    assert_eq!(solver::expected_value(&dealt, &deck[..1], &[0, 1, 2, 3], payoff), Some(1f64));
+
+    // one card left: only the holds discarding at most one card.
+    let options = solver::solve(&dealt, &deck[..1], DrawRule::ANY, payoff);
+    assert_eq!(options.len(), 6);
+    assert!(options.iter().all(|option| option.expected_value.is_finite()));
+
+    // no cards left: keeping the hand is the only option.
+    let optimal = solver::optimal(&dealt, &[], DrawRule::ANY, payoff);
+    assert_eq!((optimal.keep, optimal.expected_value), (vec![0, 1, 2, 3, 4], 0f64));
+}
@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+
+use poker_base::{parse_cards, parse_hand, Deck, Paytable, Variant};
+use poker_strategy::video_poker;
+
+fn optimal(cards: &str, paytable: &Paytable) -> poker_base::ComputedMove {
+    let shown = parse_hand(cards).unwrap();
+    let remaining = Deck::standard().remaining(&shown);
+
+    video_poker::calculate_optimal(&Variant::JacksOrBetter, paytable, &remaining, &shown)
+}
+
+#[test]
+fn dealt_royal_flush_is_kept() {
+    let optimal = optimal("Th Jh Qh Kh Ah", &Paytable::jacks_or_better_9_6());
+
+    assert_eq!(optimal.keep, vec![0, 1, 2, 3, 4]);
+    assert_eq!(optimal.average_score, 800f64);
+    assert_eq!(optimal.distribution, BTreeMap::from([(800, 1)]));
+}
+
+/// Four to a royal flush without penalty cards: one royal flush (800), eight flushes (6), three straights (4)
+/// and twelve high pairs (1) among the 47 draws.
+#[test]
+fn four_to_a_royal_flush() {
+    let optimal = optimal("Ah Kh Qh Jh 2c", &Paytable::jacks_or_better_9_6());
+
+    assert_eq!(optimal.keep, vec![0, 1, 2, 3]);
+    assert!((optimal.average_score - 872f64 / 47f64).abs() < 1e-12, "average score was {}", optimal.average_score);
+    assert_eq!(optimal.distribution.values().sum::<u64>(), 47);
+}
+
+/// A low pair beats four to an outside straight.
+#[test]
+fn low_pair_over_outside_straight() {
+    let optimal = optimal("5h 5c 6d 7s 8h", &Paytable::jacks_or_better_9_6());
+
+    assert_eq!(optimal.keep, vec![0, 1]);
+}
+
+#[test]
+fn average_score_of_distribution() {
+    assert_eq!(video_poker::calculate_avg_score(&BTreeMap::from([(0, 3), (4, 1)])), 1f64);
+}
+
+/// With the ten of hearts (and an irrelevant nine) dead, four to a royal flush can only make eight flushes (6), three straights (4)
+/// and twelve high pairs (1) among the 45 draws.
+#[test]
+fn dead_card_removes_the_royal_draw() {
+    let shown = parse_hand("Ah Kh Qh Jh 2c").unwrap();
+    let dead = parse_cards("Th 9s").unwrap();
+
+    let r#move = video_poker::calculate_dead_card_move(&Variant::JacksOrBetter, &Paytable::jacks_or_better_9_6(), &shown, &dead);
+
+    assert!(!r#move.hold_changed());
+    assert!((r#move.adjusted.average_score - 72f64 / 45f64).abs() < 1e-12, "average score was {}", r#move.adjusted.average_score);
+    assert!((r#move.difference() - (72f64 / 45f64 - 872f64 / 47f64)).abs() < 1e-12);
+    assert_eq!(r#move.adjusted.distribution.values().sum::<u64>(), 45);
+}
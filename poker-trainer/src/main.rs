@@ -0,0 +1,199 @@
+use std::{env, error::Error, fmt, io::{self, BufRead, Write}, time::Instant};
+
+use rand::seq::SliceRandom;
+
+use poker_analysis::{draw::DrawTable, state, strategy::Strategy};
+use poker_base::{game, Card, ComputedMove};
+
+/// The EV difference below which two holds count as equally good.
+const TOLERANCE: f64 = 1e-9;
+
+/// The decisions taken during a training session.
+#[derive(Default)]
+struct Session {
+    hands: usize,
+    correct: usize,
+    /// The summed EV lost by the mistakes, in coins per coin bet.
+    lost: f64,
+    /// The most expensive mistake: the hand, the held cards and the EV lost.
+    worst: Option<([Card; 5], Vec<usize>, f64)>,
+}
+
+impl Session {
+    fn record(&mut self, hand: &[Card; 5], keep: &[usize], lost: f64) {
+        self.hands += 1;
+
+        if lost <= TOLERANCE {
+            self.correct += 1;
+
+            return;
+        }
+
+        self.lost += lost;
+
+        if self.worst.as_ref().is_none_or(|(_, _, worst)| lost > *worst) {
+            self.worst = Some((*hand, keep.to_vec(), lost));
+        }
+    }
+
+    fn accuracy(&self) -> f64 {
+        self.correct as f64 / self.hands.max(1) as f64 * 100f64
+    }
+}
+
+impl fmt::Display for Session {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter, "Accuracy {}/{} ({:.1}%), EV lost {:.4} ({:.4} per hand)",
+            self.correct, self.hands, self.accuracy(), self.lost, self.lost / self.hands.max(1) as f64
+        )
+    }
+}
+
+/// The held cards, e.g. `A♥ K♥`.
+fn cards(hand: &[Card; 5], keep: &[usize]) -> String {
+    match keep.is_empty() {
+        true => "nothing".to_string(),
+        false => keep.iter().map(|&index| hand[index].to_string()).collect::<Vec<_>>().join(" "),
+    }
+}
+
+/// Parses the positions (1 to 5) of the held cards, e.g. `134` or `1 3 4`. A lone `-` holds nothing.
+fn parse_hold(input: &str) -> Option<Vec<usize>> {
+    if input == "-" {
+        return Some(Vec::new());
+    }
+
+    let mut keep = Vec::new();
+
+    for position in input.chars().filter(|char| !char.is_whitespace() && *char != ',') {
+        let index = position.to_digit(10).filter(|position| (1..=5).contains(position))? as usize - 1;
+
+        if keep.contains(&index) {
+            return None;
+        }
+
+        keep.push(index);
+    }
+
+    keep.sort_unstable();
+
+    (!keep.is_empty()).then_some(keep)
+}
+
+/// The optimal move of the strategy file, falling back to solving the hand if the file does not cover it.
+fn optimal(table: &DrawTable, strategy: Option<&Strategy>, hand: &[Card; 5]) -> ComputedMove {
+    strategy.and_then(|strategy| strategy.lookup(hand)).unwrap_or_else(|| table.optimal(hand))
+}
+
+fn start(game_name: &str, file: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let (variant, paytable) = game::resolve(game_name)?;
+
+    log::info!("Tabulating final hands of `{variant}` for paytable `{}`...", paytable.name);
+    let start = Instant::now();
+    let table = DrawTable::new(variant, &paytable);
+    log::info!("Tabulated final hands in {}ms.", start.elapsed().as_millis());
+
+    let strategy = match file {
+        Some(file) => Some(Strategy::new(state::load_variant_moves(file, variant)?)),
+        None => None,
+    };
+
+    println!("Training `{variant}` with paytable `{}`.", paytable.name);
+    println!("Enter the positions (1-5) of the cards to hold, e.g. `134`, `-` to hold nothing or `q` to quit.");
+
+    let mut deck = table.deck().to_vec();
+    let mut rng = rand::thread_rng();
+    let mut session = Session::default();
+    let mut lines = io::stdin().lock().lines();
+
+    'training: loop {
+        deck.shuffle(&mut rng);
+
+        let mut hand: [Card; 5] = deck[..5].try_into().unwrap();
+        hand.sort_unstable_by(|a, b| b.cmp(a));
+
+        println!();
+        println!("Hand #{}", session.hands + 1);
+        println!("{}", (1..=5).map(|position| format!("{position:>4}")).collect::<String>());
+        println!("{}", hand.iter().map(|card| format!("{:>4}", card.to_string())).collect::<String>());
+
+        let keep = loop {
+            print!("Hold: ");
+            io::stdout().flush()?;
+
+            let Some(line) = lines.next() else { break 'training };
+            let line = line?;
+
+            match line.trim() {
+                "q" => break 'training,
+                input => match parse_hold(input) {
+                    Some(keep) => break keep,
+                    None => println!("Invalid hold `{input}`: enter positions 1-5, `-` or `q`."),
+                },
+            }
+        };
+
+        let optimal = optimal(&table, strategy.as_ref(), &hand);
+
+        let held = table.outcome(&hand, &keep).expected_value(&paytable);
+        let best = table.outcome(&hand, &optimal.keep).expected_value(&paytable);
+        let lost = (best - held).max(0f64);
+
+        match lost <= TOLERANCE {
+            true if keep == optimal.keep => println!("Correct: holding {} (EV {held:.4}).", cards(&hand, &keep)),
+            true => println!("Correct: holding {} (EV {held:.4}) is as good as {}.", cards(&hand, &keep), cards(&hand, &optimal.keep)),
+            false => println!(
+                "Mistake: holding {} (EV {held:.4}) instead of {} (EV {best:.4}) costs {lost:.4}.",
+                cards(&hand, &keep), cards(&hand, &optimal.keep)
+            ),
+        }
+
+        session.record(&hand, &keep, lost);
+
+        println!("{session}");
+    }
+
+    println!();
+    println!("Session: {session}");
+
+    if let Some((hand, keep, lost)) = &session.worst {
+        println!("Worst mistake: holding {} of {} cost {lost:.4}.", cards(hand, keep), cards(hand, &[0, 1, 2, 3, 4]));
+    }
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(error) = simple_logger::SimpleLogger::new().env().init() {
+        eprintln!("Logger initialization failed: {}", error);
+
+        std::process::exit(1);
+    }
+
+    let args: Vec<String> = env::args().collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        [_, game] => start(game, None),
+        [_, game, file] => start(game, Some(file)),
+        [binary, ..] => {
+            usage(binary);
+
+            std::process::exit(1);
+        },
+        [] => unreachable!(),
+    };
+
+    if let Err(error) = result {
+        log::error!("Fatal: {}", error);
+
+        std::process::exit(1);
+    }
+}
+
+fn usage(binary: &str) {
+    log::error!("Usage: {binary} <game> [state file]");
+    log::error!("A game is a variant, a paytable preset or `<variant>:<paytable (preset or JSON file)>`.");
+    log::error!("Without a state file, the optimal holds are solved on the fly.");
+}